@@ -0,0 +1,150 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::policy::{RuleReport, coded_issues, retain_issues};
+use crate::representation::{Issue, IssueKey};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+pub const BASELINE_VERSION: u32 = 1;
+
+// Snapshot of known issues. Matching ones are suppressed on later runs so only
+// newly introduced issues are reported, entries which no longer match are
+// reported as fixed so the file can shrink over time.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: BTreeSet<BaselineEntry>,
+}
+
+// Known issues are matched by their identity across runs
+pub type BaselineEntry = IssueKey;
+
+#[derive(Debug, Default, Serialize)]
+pub struct BaselineOutcome {
+    pub suppressed: usize,
+    pub fixed: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn from_issues(graph_issues: &[Issue], reports: &[RuleReport<'_>]) -> Self {
+        let entries = coded_issues(graph_issues, reports)
+            .map(|(code, issue)| BaselineEntry::new(code, issue))
+            .collect();
+        Self {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    // Drops every issue present in the baseline
+    pub fn apply(
+        &self,
+        graph_issues: &mut Vec<Issue>,
+        reports: &mut [RuleReport<'_>],
+    ) -> BaselineOutcome {
+        let mut matched: BTreeSet<BaselineEntry> = BTreeSet::new();
+        let mut suppressed = 0;
+
+        retain_issues(graph_issues, reports, |code, issue| {
+            let entry = BaselineEntry::new(code, issue);
+            if self.entries.contains(&entry) {
+                matched.insert(entry);
+                suppressed += 1;
+                false
+            } else {
+                true
+            }
+        });
+
+        BaselineOutcome {
+            suppressed,
+            fixed: self.entries.difference(&matched).cloned().collect(),
+        }
+    }
+
+    pub fn to_string_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::representation::{IssueCode, NodeId};
+
+    #[test]
+    fn baseline_suppresses_known_and_reports_fixed() {
+        let known = vec![
+            Issue::error(IssueCode::BrokenLink, NodeId::from("A"), "edge A -> X"),
+            Issue::warn(IssueCode::DuplicateLink, NodeId::from("B"), "dup"),
+        ];
+        let baseline = Baseline::from_issues(&known, &[]);
+        assert_eq!(baseline.entries.len(), 2);
+
+        let mut current = vec![
+            Issue::error(IssueCode::BrokenLink, NodeId::from("A"), "Edge  A -> X "),
+            Issue::error(IssueCode::BrokenLink, NodeId::from("C"), "edge C -> Y"),
+        ];
+        let outcome = baseline.apply(&mut current, &mut []);
+
+        assert_eq!(outcome.suppressed, 1);
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].subject, NodeId::from("C"));
+        assert_eq!(outcome.fixed.len(), 1);
+        assert_eq!(outcome.fixed[0].subject, NodeId::from("B"));
+    }
+
+    #[test]
+    fn baseline_roundtrip() {
+        let known = vec![Issue::error(
+            IssueCode::BrokenLink,
+            NodeId::from("A"),
+            "edge",
+        )];
+        let baseline = Baseline::from_issues(&known, &[]);
+        let raw = baseline.to_string_pretty().unwrap();
+        let back: Baseline = serde_json::from_str(&raw).unwrap();
+        assert_eq!(back, baseline);
+    }
+
+    #[test]
+    fn baselined_reach_issue_stays_suppressed_while_targets_change() {
+        use crate::io::{load_policy_from_str, populate_from_str};
+        use crate::policy::evaluate_rules;
+        use crate::representation::Graph;
+
+        let ds = populate_from_str(
+            r#"{ "current_version": "2", "versions": {
+                "1": { "needs": {
+                  "REQ_1": { "id":"REQ_1","type":"req","links":[] },
+                  "TEST_1": { "id":"TEST_1","type":"test","links":[] } } },
+                "2": { "needs": {
+                  "REQ_1": { "id":"REQ_1","type":"req","links":["TEST_1"] },
+                  "TEST_1": { "id":"TEST_1","type":"test","links":[] } } }
+            } }"#,
+        )
+        .unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [ { "rule_id": "reach_kind", "selector": { "kinds": ["req"] },
+                 "params": { "target_kinds": ["test"], "min": 2 } } ] }"#,
+        )
+        .unwrap();
+        let before = Graph::new(ds.access_version("1").unwrap()).unwrap();
+        let reports = evaluate_rules(&before, &policy).unwrap();
+        let baseline = Baseline::from_issues(&[], &reports);
+
+        // Still one test short, the detail now lists TEST_1
+        let after = Graph::new(ds.access_version("2").unwrap()).unwrap();
+        let mut reports = evaluate_rules(&after, &policy).unwrap();
+        let outcome = baseline.apply(&mut Vec::new(), &mut reports);
+
+        assert_eq!(outcome.suppressed, 1);
+        assert!(outcome.fixed.is_empty());
+        assert!(reports[0].issues.is_empty());
+    }
+}
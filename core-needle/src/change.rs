@@ -0,0 +1,211 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::diff::Diff;
+use crate::error::Result;
+use crate::policy::{Policies, coded_issues, evaluate_rules};
+use crate::representation::{Graph, Issue, IssueKey, NodeId};
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+// Issues of a change rather than of the whole project: the ones the head
+// export introduces, the ones it fixes, and the ones which were there before
+// but sit on needs the change touches.
+#[derive(Debug, Default, Serialize)]
+pub struct ChangeReport {
+    pub introduced: Vec<ChangedIssue>,
+    pub fixed: Vec<ChangedIssue>,
+    pub touched: Vec<ChangedIssue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedIssue {
+    // Policy rule code, or the issue code for issues found while building the graph
+    pub rule_code: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+impl ChangedIssue {
+    pub fn key(&self) -> IssueKey {
+        IssueKey::new(&self.rule_code, &self.issue)
+    }
+}
+
+impl ChangeReport {
+    // Runs the policy on both graphs
+    pub fn evaluate(base: &Graph, head: &Graph, policy: &Policies, diff: &Diff) -> Result<Self> {
+        Ok(Self::between(
+            issues(base, policy)?,
+            issues(head, policy)?,
+            diff,
+        ))
+    }
+
+    pub fn between(base: Vec<ChangedIssue>, head: Vec<ChangedIssue>, diff: &Diff) -> Self {
+        let base_keys: BTreeSet<IssueKey> = base.iter().map(ChangedIssue::key).collect();
+        let head_keys: BTreeSet<IssueKey> = head.iter().map(ChangedIssue::key).collect();
+        let touched_needs = diff.touched();
+        let on_touched = |i: &ChangedIssue| {
+            std::iter::once(&i.issue.subject)
+                .chain(&i.issue.related)
+                .any(|id| touched_needs.contains(id))
+        };
+
+        let mut out = Self::default();
+        for issue in head {
+            if !base_keys.contains(&issue.key()) {
+                out.introduced.push(issue);
+            } else if on_touched(&issue) {
+                out.touched.push(issue);
+            }
+        }
+        out.fixed = base
+            .into_iter()
+            .filter(|i| !head_keys.contains(&i.key()))
+            .collect();
+
+        for list in [&mut out.introduced, &mut out.fixed, &mut out.touched] {
+            list.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.introduced.is_empty() && self.fixed.is_empty() && self.touched.is_empty()
+    }
+}
+
+fn sort_key(i: &ChangedIssue) -> (&NodeId, &str, &str) {
+    (&i.issue.subject, &i.rule_code, &i.issue.detail)
+}
+
+// Graph issues together with the ones of every policy rule
+fn issues(g: &Graph, policy: &Policies) -> Result<Vec<ChangedIssue>> {
+    let reports = evaluate_rules(g, policy)?;
+    Ok(coded_issues(&g.issues, &reports)
+        .map(|(code, issue)| ChangedIssue {
+            rule_code: code.to_string(),
+            issue: issue.clone(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::DiffOptions;
+    use crate::io::{load_policy_from_str, populate_from_str};
+
+    const NEEDS: &str = r#"{
+      "current_version": "head",
+      "versions": {
+        "base": { "needs": {
+          "REQ_1": { "id":"REQ_1","type":"req","links":[] },
+          "REQ_2": { "id":"REQ_2","type":"req","links":[] },
+          "REQ_3": { "id":"REQ_3","type":"req","links":[] },
+          "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+        } },
+        "head": { "needs": {
+          "REQ_1": { "id":"REQ_1","type":"req","links":["TEST_1"] },
+          "REQ_2": { "id":"REQ_2","type":"req","title":"Reworded","links":[] },
+          "REQ_3": { "id":"REQ_3","type":"req","links":[] },
+          "REQ_4": { "id":"REQ_4","type":"req","links":[] },
+          "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+        } }
+      }
+    }"#;
+
+    #[test]
+    fn introduced_fixed_and_touched_issues() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let (base_view, head_view) = (
+            ds.access_version("base").unwrap(),
+            ds.access_current_version().unwrap(),
+        );
+        let diff = Diff::between(&base_view, &head_view, &DiffOptions::default());
+        let base = Graph::new(base_view).unwrap();
+        let head = Graph::new(head_view).unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [ { "rule_id": "has_outgoing",
+                 "selector": { "kinds": ["req"] }, "code": "REQ_LINKS" } ] }"#,
+        )
+        .unwrap();
+
+        let report = ChangeReport::evaluate(&base, &head, &policy, &diff).unwrap();
+        let subjects = |list: &[ChangedIssue]| -> Vec<String> {
+            list.iter()
+                .map(|i| format!("{} {}", i.rule_code, i.issue.subject))
+                .collect()
+        };
+        assert_eq!(
+            subjects(&report.introduced),
+            vec!["REQ_LINKS REQ_4", "dandling_node REQ_4"]
+        );
+        assert_eq!(
+            subjects(&report.fixed),
+            vec![
+                "REQ_LINKS REQ_1",
+                "dandling_node REQ_1",
+                "dandling_node TEST_1"
+            ]
+        );
+        // REQ_3 is left as it was
+        assert_eq!(
+            subjects(&report.touched),
+            vec!["REQ_LINKS REQ_2", "dandling_node REQ_2"]
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["introduced"][0]["rule_code"], "REQ_LINKS");
+        assert_eq!(json["introduced"][0]["subject"], "REQ_4");
+        assert_eq!(json["introduced"][0]["code"], "REQ_LINKS");
+    }
+
+    #[test]
+    fn issue_persists_when_its_detail_changes() {
+        let ds = populate_from_str(
+            r#"{
+              "current_version": "head",
+              "versions": {
+                "base": { "needs": {
+                  "REQ_1": { "id":"REQ_1","type":"req","links":[] },
+                  "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+                } },
+                "head": { "needs": {
+                  "REQ_1": { "id":"REQ_1","type":"req","links":["TEST_1"] },
+                  "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+                } }
+              }
+            }"#,
+        )
+        .unwrap();
+        let (base_view, head_view) = (
+            ds.access_version("base").unwrap(),
+            ds.access_current_version().unwrap(),
+        );
+        let diff = Diff::between(&base_view, &head_view, &DiffOptions::default());
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [ { "rule_id": "reach_kind", "selector": { "kinds": ["req"] },
+                 "params": { "target_kinds": ["test"], "min": 2 }, "code": "REQ_TESTS" } ] }"#,
+        )
+        .unwrap();
+        let (base, head) = (
+            Graph::new(base_view).unwrap(),
+            Graph::new(head_view).unwrap(),
+        );
+
+        // One test of two is there now, the issue is still the same one
+        let report = ChangeReport::evaluate(&base, &head, &policy, &diff).unwrap();
+        let codes = |list: &[ChangedIssue]| -> Vec<String> {
+            list.iter().map(|i| i.rule_code.clone()).collect()
+        };
+        assert!(!codes(&report.introduced).contains(&"REQ_TESTS".to_string()));
+        assert!(!codes(&report.fixed).contains(&"REQ_TESTS".to_string()));
+        assert!(codes(&report.touched).contains(&"REQ_TESTS".to_string()));
+    }
+}
@@ -0,0 +1,302 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::representation::{LinkType, Node, NodeId, VersionAccessor};
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+// Bookkeeping options of the Sphinx-Needs export, they change whenever the
+// document around a need does and would bury the real changes
+pub const IGNORED_FIELDS: &[&str] = &["lineno", "lineno_content", "is_modified", "modifications"];
+
+// Typed need options, everything else is read from `Node::extra`
+const TYPED_FIELDS: &[&str] = &["type", "title", "status", "url", "tags"];
+
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    // Options never reported as changed
+    pub ignore_fields: Vec<String>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            ignore_fields: IGNORED_FIELDS.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
+// Changes between two versions of the needs, e.g the export of the main
+// branch (`base`) and the one of a merge request (`head`)
+#[derive(Debug, Default, Serialize)]
+pub struct Diff {
+    pub base_version: String,
+    pub head_version: String,
+    pub added: Vec<NodeId>,
+    pub removed: Vec<NodeId>,
+    pub modified: Vec<NeedDiff>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NeedDiff {
+    pub id: NodeId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links_added: Vec<LinkChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links_removed: Vec<LinkChange>,
+}
+
+// Missing and `null` options are both `None`
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub base: Option<Value>,
+    pub head: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct LinkChange {
+    pub link_type: LinkType,
+    pub target: NodeId,
+}
+
+impl Diff {
+    pub fn between(
+        base: &VersionAccessor<'_>,
+        head: &VersionAccessor<'_>,
+        options: &DiffOptions,
+    ) -> Self {
+        let mut added: Vec<NodeId> = head
+            .needs
+            .keys()
+            .filter(|id| !base.needs.contains_key(*id))
+            .cloned()
+            .collect();
+        let mut removed: Vec<NodeId> = base
+            .needs
+            .keys()
+            .filter(|id| !head.needs.contains_key(*id))
+            .cloned()
+            .collect();
+        added.sort();
+        removed.sort();
+
+        let mut modified: Vec<NeedDiff> = base
+            .needs
+            .iter()
+            .filter_map(|(id, old)| {
+                let new = head.needs.get(id)?;
+                NeedDiff::between(id, old, new, options)
+            })
+            .collect();
+        modified.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Self {
+            base_version: base.version.to_string(),
+            head_version: head.version.to_string(),
+            added,
+            removed,
+            modified,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    // Needs present in both versions and changed, or only in one of them
+    pub fn touched(&self) -> BTreeSet<&NodeId> {
+        self.added
+            .iter()
+            .chain(&self.removed)
+            .chain(self.modified.iter().map(|m| &m.id))
+            .collect()
+    }
+}
+
+impl NeedDiff {
+    fn between(id: &NodeId, base: &Node, head: &Node, options: &DiffOptions) -> Option<Self> {
+        let link_types: BTreeSet<LinkType> = base
+            .link_types()
+            .into_iter()
+            .chain(head.link_types())
+            .collect();
+
+        let base_fields = fields(base, &link_types, options);
+        let head_fields = fields(head, &link_types, options);
+        let names: BTreeSet<&String> = base_fields.keys().chain(head_fields.keys()).collect();
+        let fields: Vec<FieldChange> = names
+            .into_iter()
+            .filter_map(|name| {
+                let (old, new) = (base_fields.get(name), head_fields.get(name));
+                (old != new).then(|| FieldChange {
+                    field: name.clone(),
+                    base: old.cloned(),
+                    head: new.cloned(),
+                })
+            })
+            .collect();
+
+        let base_links = links(base, &link_types);
+        let head_links = links(head, &link_types);
+        let links_added: Vec<LinkChange> = head_links.difference(&base_links).cloned().collect();
+        let links_removed: Vec<LinkChange> = base_links.difference(&head_links).cloned().collect();
+
+        if fields.is_empty() && links_added.is_empty() && links_removed.is_empty() {
+            return None;
+        }
+        Some(Self {
+            id: id.clone(),
+            fields,
+            links_added,
+            links_removed,
+        })
+    }
+}
+
+// Every option but the links, which are compared as such
+fn fields(
+    node: &Node,
+    link_types: &BTreeSet<LinkType>,
+    options: &DiffOptions,
+) -> BTreeMap<String, Value> {
+    let is_link = |name: &str| {
+        link_types
+            .iter()
+            .any(|lt| lt.as_str() == name || lt.back_field() == name)
+    };
+    TYPED_FIELDS
+        .iter()
+        .map(|f| f.to_string())
+        .chain(node.extra.keys().cloned())
+        .filter(|name| !is_link(name) && !options.ignore_fields.contains(name))
+        .filter_map(|name| match node.field_value(&name) {
+            None | Some(Value::Null) => None,
+            Some(value) => Some((name, value)),
+        })
+        .collect()
+}
+
+// Forward links only, backward ones are derived from the other needs
+fn links(node: &Node, link_types: &BTreeSet<LinkType>) -> BTreeSet<LinkChange> {
+    link_types
+        .iter()
+        .flat_map(|lt| {
+            node.linked(lt).into_iter().map(|target| LinkChange {
+                link_type: lt.clone(),
+                target: NodeId::from(target),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+    use serde_json::json;
+
+    const NEEDS: &str = r#"{
+      "current_version": "2.0",
+      "versions": {
+        "1.0": { "needs": {
+          "REQ_1": { "id":"REQ_1","type":"req","title":"Brake","status":"open","lineno":10,
+                     "links":["SPEC_1"],"implements":[],"implements_back":[] },
+          "SPEC_1": { "id":"SPEC_1","type":"spec","links":[],"links_back":["REQ_1"] },
+          "OLD_1": { "id":"OLD_1","type":"req","links":[] }
+        } },
+        "2.0": { "needs": {
+          "REQ_1": { "id":"REQ_1","type":"req","title":"Brake hard","lineno":12,"asil":"B",
+                     "links":[],"implements":["SPEC_1"],"implements_back":[] },
+          "SPEC_1": { "id":"SPEC_1","type":"spec","links":[],"links_back":[],"lineno":3 },
+          "NEW_1": { "id":"NEW_1","type":"test","links":["REQ_1"] }
+        } }
+      }
+    }"#;
+
+    #[test]
+    fn added_removed_and_modified_needs() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let base = ds.access_version("1.0").unwrap();
+        let head = ds.access_current_version().unwrap();
+
+        let diff = Diff::between(&base, &head, &DiffOptions::default());
+        assert_eq!(diff.base_version, "1.0");
+        assert_eq!(diff.added, vec![NodeId::from("NEW_1")]);
+        assert_eq!(diff.removed, vec![NodeId::from("OLD_1")]);
+
+        // SPEC_1 only changed its back links and bookkeeping options
+        assert_eq!(diff.modified.len(), 1);
+        let req = &diff.modified[0];
+        assert_eq!(req.id, NodeId::from("REQ_1"));
+        assert_eq!(
+            req.fields,
+            vec![
+                FieldChange {
+                    field: "asil".into(),
+                    base: None,
+                    head: Some(json!("B")),
+                },
+                FieldChange {
+                    field: "status".into(),
+                    base: Some(json!("open")),
+                    head: None,
+                },
+                FieldChange {
+                    field: "title".into(),
+                    base: Some(json!("Brake")),
+                    head: Some(json!("Brake hard")),
+                },
+            ]
+        );
+        let link = |lt: &str| LinkChange {
+            link_type: LinkType::from(lt),
+            target: NodeId::from("SPEC_1"),
+        };
+        assert_eq!(req.links_added, vec![link("implements")]);
+        assert_eq!(req.links_removed, vec![link("links")]);
+        assert_eq!(diff.touched().len(), 3);
+
+        let same = Diff::between(&head, &head, &DiffOptions::default());
+        assert!(same.is_empty());
+    }
+
+    #[test]
+    fn ignored_fields_are_configurable() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let base = ds.access_version("1.0").unwrap();
+        let head = ds.access_current_version().unwrap();
+
+        let diff = Diff::between(
+            &base,
+            &head,
+            &DiffOptions {
+                ignore_fields: vec!["title".into()],
+            },
+        );
+        let changed: Vec<(&str, Vec<&str>)> = diff
+            .modified
+            .iter()
+            .map(|m| {
+                (
+                    m.id.as_ref(),
+                    m.fields.iter().map(|f| f.field.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                ("REQ_1", vec!["asil", "lineno", "status"]),
+                ("SPEC_1", vec!["lineno"])
+            ]
+        );
+    }
+}
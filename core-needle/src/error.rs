@@ -0,0 +1,137 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::representation::NodeId;
+use std::fmt;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// Everything which makes loading or evaluation impossible. Problems found in
+// the data itself are reported as `Issue`s instead - those never stop the run.
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Json {
+        path: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        source: serde_json::Error,
+    },
+    // `current_version` is absent, or names a version which is not in `versions`
+    MissingVersion {
+        version: Option<String>,
+    },
+    MissingType {
+        id: NodeId,
+    },
+    // Need asked for by the caller, e.g a root of the exported subgraph
+    UnknownNeed {
+        id: NodeId,
+    },
+    InvalidRuleParams {
+        rule_id: String,
+        reason: String,
+    },
+    // Policy validation found problems and the caller asked not to run anyway
+    InvalidPolicy {
+        problems: usize,
+    },
+}
+
+impl Error {
+    pub fn json(path: Option<PathBuf>, source: serde_json::Error) -> Self {
+        Self::Json {
+            path,
+            line: source.line(),
+            column: source.column(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                write!(f, "can't read {}: {}", path.display(), source)
+            }
+            Self::Json {
+                path,
+                line,
+                column,
+                source,
+            } => {
+                let origin = path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "<input>".to_string());
+                write!(f, "invalid JSON in {origin}:{line}:{column}: {source}")
+            }
+            Self::MissingVersion { version: None } => {
+                write!(f, "needs export has no `current_version`")
+            }
+            Self::MissingVersion {
+                version: Some(version),
+            } => write!(f, "version `{version}` not found in needs export"),
+            Self::MissingType { id } => write!(f, "need `{id}` has no `type`"),
+            Self::UnknownNeed { id } => write!(f, "need `{id}` not found in needs export"),
+            Self::InvalidRuleParams { rule_id, reason } => {
+                write!(f, "invalid params for rule `{rule_id}`: {reason}")
+            }
+            Self::InvalidPolicy { problems } => {
+                write!(f, "policy is invalid ({problems} problem(s) found)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Json { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_error_keeps_position() {
+        let source = serde_json::from_str::<serde_json::Value>("{\n  \"a\": ]").unwrap_err();
+        let err = Error::json(Some(PathBuf::from("needs.json")), source);
+
+        match &err {
+            Error::Json { line, column, .. } => {
+                assert_eq!(*line, 2);
+                assert_eq!(*column, 8);
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+        assert!(
+            err.to_string()
+                .starts_with("invalid JSON in needs.json:2:8")
+        );
+    }
+
+    #[test]
+    fn missing_version_display() {
+        let err = Error::MissingVersion { version: None };
+        assert_eq!(err.to_string(), "needs export has no `current_version`");
+
+        let err = Error::MissingVersion {
+            version: Some("2.0".to_string()),
+        };
+        assert_eq!(err.to_string(), "version `2.0` not found in needs export");
+    }
+}
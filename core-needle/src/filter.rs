@@ -0,0 +1,608 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+// Sphinx-Needs filter strings, the Python-like expressions used by `needtable`,
+// `needflow` and friends, e.g.
+//
+//   type == 'req' and status != 'rejected' and 'safety' in tags
+//
+// Supported: string/number/bool/None literals, lists, need options as names,
+// `== != < <= > >=`, `in`, `not in`, `and`, `or`, `not`, parentheses,
+// `search(pattern, value)`, `len(value)` and the `startswith`, `endswith`,
+// `lower`, `upper` string methods. Type mismatches evaluate to false rather
+// than failing like Python would, unknown functions and methods as well as
+// invalid regex literals are rejected when parsing.
+
+use crate::representation::Node;
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value as Json;
+use std::fmt;
+
+// Name and number of arguments
+const FUNCTIONS: &[(&str, usize)] = &[("search", 2), ("len", 1)];
+const METHODS: &[(&str, usize)] = &[
+    ("startswith", 1),
+    ("endswith", 1),
+    ("lower", 0),
+    ("upper", 0),
+];
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    raw: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError {
+    // Character offset in the filter string
+    pub pos: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.pos)
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+impl Filter {
+    pub fn parse(raw: &str) -> Result<Self, FilterError> {
+        let tokens = lex(raw)?;
+        let mut p = Parser { tokens, at: 0 };
+        let expr = p.or()?;
+        match p.peek() {
+            Tok::End => Ok(Self {
+                raw: raw.to_string(),
+                expr,
+            }),
+            _ => Err(p.error("unexpected token")),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn matches(&self, node: &Node) -> bool {
+        eval(&self.expr, node).truthy()
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(d)?;
+        Self::parse(&raw).map_err(|e| serde::de::Error::custom(format!("invalid filter: {e}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Name(String),
+    Str(String),
+    Num(f64),
+    Op(CmpOp),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+    End,
+}
+
+fn lex(raw: &str) -> Result<Vec<(usize, Tok)>, FilterError> {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let tok = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => Tok::LParen,
+            ')' => Tok::RParen,
+            '[' => Tok::LBracket,
+            ']' => Tok::RBracket,
+            ',' => Tok::Comma,
+            '.' if !chars.get(i + 1).is_some_and(char::is_ascii_digit) => Tok::Dot,
+            '=' | '!' | '<' | '>' => {
+                let eq = chars.get(i + 1) == Some(&'=');
+                let op = match (c, eq) {
+                    ('=', true) => CmpOp::Eq,
+                    ('!', true) => CmpOp::Ne,
+                    ('<', true) => CmpOp::Le,
+                    ('>', true) => CmpOp::Ge,
+                    ('<', false) => CmpOp::Lt,
+                    ('>', false) => CmpOp::Gt,
+                    _ => {
+                        return Err(FilterError {
+                            pos: i,
+                            message: format!("unexpected `{c}`"),
+                        });
+                    }
+                };
+                i += if eq { 2 } else { 1 };
+                out.push((start, Tok::Op(op)));
+                continue;
+            }
+            '\'' | '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(FilterError {
+                                pos: start,
+                                message: "unterminated string".to_string(),
+                            });
+                        }
+                        Some(&q) if q == c => break,
+                        Some('\\') if i + 1 < chars.len() => {
+                            s.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&ch) => {
+                            s.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                out.push((start, Tok::Str(s)));
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' || c == '-' => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end].is_ascii_digit() || chars[end] == '.') {
+                    end += 1;
+                }
+                let text: String = chars[i..end].iter().collect();
+                let num = text.parse().map_err(|_| FilterError {
+                    pos: i,
+                    message: format!("invalid number `{text}`"),
+                })?;
+                i = end;
+                out.push((start, Tok::Num(num)));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let name: String = chars[i..end].iter().collect();
+                i = end;
+                out.push((start, Tok::Name(name)));
+                continue;
+            }
+            other => {
+                return Err(FilterError {
+                    pos: i,
+                    message: format!("unexpected `{other}`"),
+                });
+            }
+        };
+        i += 1;
+        out.push((start, tok));
+    }
+
+    out.push((chars.len(), Tok::End));
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    NotIn,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Lit(Val),
+    Field(String),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Cmp(CmpOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    // `search` with a literal pattern, compiled once
+    Search(Regex, Box<Expr>),
+    Method(Box<Expr>, String, Vec<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<(usize, Tok)>,
+    at: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.at].1
+    }
+
+    fn next(&mut self) -> Tok {
+        let tok = self.tokens[self.at].1.clone();
+        if tok != Tok::End {
+            self.at += 1;
+        }
+        tok
+    }
+
+    fn error(&self, message: &str) -> FilterError {
+        self.error_at(self.at, message)
+    }
+
+    fn error_at(&self, at: usize, message: &str) -> FilterError {
+        FilterError {
+            pos: self.tokens[at].0,
+            message: message.to_string(),
+        }
+    }
+
+    // Known function or method called with the right number of arguments
+    fn check_call(
+        &self,
+        at: usize,
+        known: &[(&str, usize)],
+        what: &str,
+        name: &str,
+        args: &[Expr],
+    ) -> Result<(), FilterError> {
+        match known.iter().find(|(n, _)| *n == name) {
+            None => Err(self.error_at(at, &format!("unknown {what} `{name}`"))),
+            Some((_, arity)) if *arity != args.len() => Err(self.error_at(
+                at,
+                &format!("`{name}` takes {arity} argument(s), got {}", args.len()),
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Tok::Name(n) if n == kw)
+    }
+
+    fn expect(&mut self, tok: Tok, what: &str) -> Result<(), FilterError> {
+        if *self.peek() == tok {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {what}")))
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.and()?;
+        while self.is_keyword("or") {
+            self.next();
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.not()?;
+        while self.is_keyword("and") {
+            self.next();
+            lhs = Expr::And(Box::new(lhs), Box::new(self.not()?));
+        }
+        Ok(lhs)
+    }
+
+    fn not(&mut self) -> Result<Expr, FilterError> {
+        if self.is_keyword("not") {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, FilterError> {
+        let lhs = self.postfix()?;
+        let op = match self.peek() {
+            Tok::Op(op) => *op,
+            Tok::Name(n) if n == "in" => CmpOp::In,
+            Tok::Name(n) if n == "not" => {
+                self.next();
+                if !self.is_keyword("in") {
+                    return Err(self.error("expected `in` after `not`"));
+                }
+                CmpOp::NotIn
+            }
+            _ => return Ok(lhs),
+        };
+        self.next();
+        let rhs = self.postfix()?;
+        Ok(Expr::Cmp(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn postfix(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.primary()?;
+        while *self.peek() == Tok::Dot {
+            self.next();
+            let at = self.at;
+            let Tok::Name(method) = self.next() else {
+                return Err(self.error("expected a method name"));
+            };
+            self.expect(Tok::LParen, "`(`")?;
+            let args = self.items(Tok::RParen)?;
+            self.check_call(at, METHODS, "method", &method, &args)?;
+            expr = Expr::Method(Box::new(expr), method, args);
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, FilterError> {
+        let pos = self.at;
+        match self.next() {
+            Tok::Str(s) => Ok(Expr::Lit(Val::Str(s))),
+            Tok::Num(n) => Ok(Expr::Lit(Val::Num(n))),
+            Tok::LParen => {
+                // `(a, b)` is a tuple, `(a)` just grouping
+                let mut items = self.items(Tok::RParen)?;
+                match items.len() {
+                    1 if !matches!(self.tokens[self.at - 2].1, Tok::Comma) => Ok(items.remove(0)),
+                    _ => Ok(Expr::List(items)),
+                }
+            }
+            Tok::LBracket => Ok(Expr::List(self.items(Tok::RBracket)?)),
+            Tok::Name(name) => match name.as_str() {
+                "True" | "true" => Ok(Expr::Lit(Val::Bool(true))),
+                "False" | "false" => Ok(Expr::Lit(Val::Bool(false))),
+                "None" => Ok(Expr::Lit(Val::None)),
+                "and" | "or" | "not" | "in" => {
+                    self.at = pos;
+                    Err(self.error(&format!("unexpected `{name}`")))
+                }
+                _ if *self.peek() == Tok::LParen => {
+                    self.next();
+                    let args = self.items(Tok::RParen)?;
+                    self.check_call(pos, FUNCTIONS, "function", &name, &args)?;
+                    self.call(pos, name, args)
+                }
+                _ => Ok(Expr::Field(name)),
+            },
+            _ => {
+                self.at = pos;
+                Err(self.error("expected a value"))
+            }
+        }
+    }
+
+    fn call(&self, at: usize, name: String, mut args: Vec<Expr>) -> Result<Expr, FilterError> {
+        if name == "search"
+            && let Expr::Lit(Val::Str(pattern)) = &args[0]
+        {
+            let re = Regex::new(pattern)
+                .map_err(|e| self.error_at(at, &format!("invalid regex `{pattern}`: {e}")))?;
+            return Ok(Expr::Search(re, Box::new(args.remove(1))));
+        }
+        Ok(Expr::Call(name, args))
+    }
+
+    // Comma separated expressions up to and including `close`
+    fn items(&mut self, close: Tok) -> Result<Vec<Expr>, FilterError> {
+        let mut items = Vec::new();
+        while *self.peek() != close {
+            items.push(self.or()?);
+            if *self.peek() == Tok::Comma {
+                self.next();
+            } else if *self.peek() != close {
+                return Err(self.error("expected `,` or a closing bracket"));
+            }
+        }
+        self.next();
+        Ok(items)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Val {
+    None,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    List(Vec<Val>),
+}
+
+impl Val {
+    fn truthy(&self) -> bool {
+        match self {
+            Val::None => false,
+            Val::Bool(b) => *b,
+            Val::Num(n) => *n != 0.0,
+            Val::Str(s) => !s.is_empty(),
+            Val::List(l) => !l.is_empty(),
+        }
+    }
+
+    fn from_json(v: &Json) -> Self {
+        match v {
+            Json::Null => Val::None,
+            Json::Bool(b) => Val::Bool(*b),
+            Json::Number(n) => n.as_f64().map_or(Val::None, Val::Num),
+            Json::String(s) => Val::Str(s.clone()),
+            Json::Array(items) => Val::List(items.iter().map(Val::from_json).collect()),
+            Json::Object(_) => Val::Str(v.to_string()),
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Val::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn eval(expr: &Expr, node: &Node) -> Val {
+    match expr {
+        Expr::Lit(v) => v.clone(),
+        Expr::Field(name) => node
+            .field_value(name)
+            .as_ref()
+            .map_or(Val::None, Val::from_json),
+        Expr::List(items) => Val::List(items.iter().map(|e| eval(e, node)).collect()),
+        Expr::Not(e) => Val::Bool(!eval(e, node).truthy()),
+        Expr::And(a, b) => Val::Bool(eval(a, node).truthy() && eval(b, node).truthy()),
+        Expr::Or(a, b) => Val::Bool(eval(a, node).truthy() || eval(b, node).truthy()),
+        Expr::Cmp(op, a, b) => Val::Bool(compare(*op, &eval(a, node), &eval(b, node))),
+        Expr::Call(name, args) => {
+            let args: Vec<Val> = args.iter().map(|e| eval(e, node)).collect();
+            call(name, &args)
+        }
+        Expr::Search(re, text) => {
+            Val::Bool(matches!(eval(text, node), Val::Str(text) if re.is_match(&text)))
+        }
+        Expr::Method(target, name, args) => {
+            let target = eval(target, node);
+            let args: Vec<Val> = args.iter().map(|e| eval(e, node)).collect();
+            method(&target, name, &args)
+        }
+    }
+}
+
+fn compare(op: CmpOp, a: &Val, b: &Val) -> bool {
+    use std::cmp::Ordering;
+
+    let ordering = match (a, b) {
+        (Val::Num(x), Val::Num(y)) => x.partial_cmp(y),
+        (Val::Str(x), Val::Str(y)) => Some(x.cmp(y)),
+        _ => None,
+    };
+    match op {
+        CmpOp::Eq => a == b,
+        CmpOp::Ne => a != b,
+        CmpOp::Lt => ordering == Some(Ordering::Less),
+        CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        CmpOp::Gt => ordering == Some(Ordering::Greater),
+        CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        CmpOp::In => contains(b, a),
+        CmpOp::NotIn => !contains(b, a),
+    }
+}
+
+fn contains(haystack: &Val, needle: &Val) -> bool {
+    match (haystack, needle) {
+        (Val::List(items), n) => items.contains(n),
+        (Val::Str(h), Val::Str(n)) => h.contains(n.as_str()),
+        _ => false,
+    }
+}
+
+fn call(name: &str, args: &[Val]) -> Val {
+    match (name, args) {
+        ("search", [Val::Str(pattern), Val::Str(text)]) => {
+            Val::Bool(Regex::new(pattern).is_ok_and(|re| re.is_match(text)))
+        }
+        ("len", [Val::Str(s)]) => Val::Num(s.chars().count() as f64),
+        ("len", [Val::List(l)]) => Val::Num(l.len() as f64),
+        _ => Val::None,
+    }
+}
+
+fn method(target: &Val, name: &str, args: &[Val]) -> Val {
+    let Some(s) = target.as_str() else {
+        return Val::None;
+    };
+    match (name, args) {
+        ("startswith", [Val::Str(p)]) => Val::Bool(s.starts_with(p.as_str())),
+        ("endswith", [Val::Str(p)]) => Val::Bool(s.ends_with(p.as_str())),
+        ("lower", []) => Val::Str(s.to_lowercase()),
+        ("upper", []) => Val::Str(s.to_uppercase()),
+        _ => Val::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node() -> Node {
+        serde_json::from_str(
+            r#"{
+                "id": "REQ_1",
+                "type": "req",
+                "title": "Brake when obstacle detected",
+                "status": "open",
+                "tags": ["safety", "adas"],
+                "priority": 2,
+                "is_external": false
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn check(filter: &str) -> bool {
+        Filter::parse(filter)
+            .unwrap_or_else(|e| panic!("`{filter}`: {e}"))
+            .matches(&node())
+    }
+
+    #[test]
+    fn sphinx_needs_examples() {
+        assert!(check(
+            "type == 'req' and status != 'rejected' and 'safety' in tags"
+        ));
+        assert!(check(r#"status in ["open", "in progress"]"#));
+        assert!(check("'comfort' not in tags"));
+        assert!(check("not is_external"));
+        assert!(check("priority >= 2 and priority < 3"));
+        assert!(check("search('^Brake', title)"));
+        assert!(check(
+            "id.startswith('REQ_') and title.lower().endswith('detected')"
+        ));
+        assert!(check(
+            "(type == 'spec' or type == 'req') and len(tags) == 2"
+        ));
+        assert!(!check("type == 'impl' or 'comfort' in tags"));
+        assert!(!check("asil == 'B'"));
+        assert!(check("asil == None"));
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_problem() {
+        let err = Filter::parse("type == 'req' and").unwrap_err();
+        assert_eq!(err.pos, 17);
+        assert!(Filter::parse("type = 'req'").is_err());
+        assert!(Filter::parse("status == 'open").is_err());
+        assert!(Filter::parse("type == 'req' status").is_err());
+    }
+
+    #[test]
+    fn unknown_calls_and_invalid_patterns_are_rejected() {
+        let err = Filter::parse("not serch('^REQ', id)").unwrap_err();
+        assert_eq!(err.pos, 4);
+        assert_eq!(err.message, "unknown function `serch`");
+        let err = Filter::parse("id.startwith('REQ_')").unwrap_err();
+        assert_eq!(err.pos, 3);
+        assert_eq!(err.message, "unknown method `startwith`");
+        assert!(Filter::parse("len(tags, title) > 1").is_err());
+        assert!(Filter::parse("title.lower('x')").is_err());
+
+        let err = Filter::parse("search('(', title)").unwrap_err();
+        assert!(err.message.starts_with("invalid regex `(`"));
+        // Patterns from need options are only known when evaluating
+        assert!(check("search(status, 'reopened')"));
+        assert!(!check("search(status, 'closed')"));
+    }
+}
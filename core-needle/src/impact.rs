@@ -0,0 +1,242 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::diff::Diff;
+use crate::error::{Error, Result};
+use crate::representation::{Direction, Graph, LinkType, NodeId, NodeKind};
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+// How far a change spreads. By default links are followed both ways, a
+// changed requirement affects its tests as much as its parent need. `Both`
+// walks up and down separately, a change never spreads from a parent to its
+// other children.
+#[derive(Debug, Clone)]
+pub struct ImpactOptions {
+    pub depth: Option<usize>,
+    pub link_types: Vec<LinkType>,
+    pub direction: Direction,
+    // Types of the affected needs listed, empty means any
+    pub kinds: Vec<NodeKind>,
+}
+
+impl Default for ImpactOptions {
+    fn default() -> Self {
+        Self {
+            depth: None,
+            link_types: Vec::new(),
+            direction: Direction::Both,
+            kinds: Vec::new(),
+        }
+    }
+}
+
+// Needs affected by a change, sorted by type, distance and id
+#[derive(Debug, Serialize)]
+pub struct Impact {
+    pub changed: Vec<NodeId>,
+    pub affected: Vec<Affected>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Affected {
+    pub id: NodeId,
+    pub kind: NodeKind,
+    // Number of links from the nearest changed need
+    pub distance: usize,
+    // Source document, e.g to list the documents which need re-approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+impl Impact {
+    // Changed needs may be missing from the graph (e.g removed by the change)
+    // as long as other needs still link to them
+    pub fn of(g: &Graph, changed: &[NodeId], options: &ImpactOptions) -> Result<Self> {
+        let mut seeds: Vec<&NodeId> = Vec::with_capacity(changed.len());
+        for id in changed {
+            let known = known(g, id).ok_or_else(|| Error::UnknownNeed { id: id.clone() })?;
+            seeds.push(known);
+        }
+        Ok(Self::walk(g, seeds, options))
+    }
+
+    // Every need the diff touches and which the head graph still knows about
+    pub fn of_diff(g: &Graph, diff: &Diff, options: &ImpactOptions) -> Self {
+        let seeds: Vec<&NodeId> = diff
+            .touched()
+            .into_iter()
+            .filter_map(|id| known(g, id))
+            .collect();
+        Self::walk(g, seeds, options)
+    }
+
+    fn walk(g: &Graph, seeds: Vec<&NodeId>, options: &ImpactOptions) -> Self {
+        let seeds: Vec<&NodeId> = seeds
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let directions = match options.direction {
+            Direction::Both => vec![Direction::Forward, Direction::Backward],
+            direction => vec![direction],
+        };
+        // Nearest distance over the walks
+        let mut reached: BTreeMap<&NodeId, usize> = BTreeMap::new();
+        for direction in directions {
+            for (id, distance) in g.reachable(&seeds, &options.link_types, direction, options.depth)
+            {
+                reached
+                    .entry(id)
+                    .and_modify(|d| *d = (*d).min(distance))
+                    .or_insert(distance);
+            }
+        }
+        let mut affected: Vec<Affected> = reached
+            .into_iter()
+            .filter_map(|(id, distance)| {
+                let node = g.nodes.get(id)?;
+                let kind = g.kind_of(id)?;
+                Some(Affected {
+                    id: id.clone(),
+                    kind,
+                    distance,
+                    doc: node.doc_path(),
+                })
+            })
+            .filter(|a| options.kinds.is_empty() || options.kinds.iter().any(|k| g.is_a(&a.id, k)))
+            .collect();
+        affected.sort_by(|a, b| (&a.kind, a.distance, &a.id).cmp(&(&b.kind, b.distance, &b.id)));
+
+        Self {
+            changed: seeds.into_iter().cloned().collect(),
+            affected,
+        }
+    }
+
+    // Type -> distance -> needs
+    pub fn by_kind(&self) -> BTreeMap<&NodeKind, BTreeMap<usize, Vec<&NodeId>>> {
+        let mut out: BTreeMap<&NodeKind, BTreeMap<usize, Vec<&NodeId>>> = BTreeMap::new();
+        for a in &self.affected {
+            out.entry(&a.kind)
+                .or_default()
+                .entry(a.distance)
+                .or_default()
+                .push(&a.id);
+        }
+        out
+    }
+
+    // Source documents of the affected needs, sorted
+    pub fn documents(&self) -> BTreeSet<&str> {
+        self.affected
+            .iter()
+            .filter_map(|a| a.doc.as_deref())
+            .collect()
+    }
+}
+
+fn known<'a>(g: &'a Graph, id: &NodeId) -> Option<&'a NodeId> {
+    g.nodes
+        .get_key_value(id)
+        .map(|(known, _)| known)
+        .or_else(|| g.reverse.get_key_value(id).map(|(known, _)| known))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::DiffOptions;
+    use crate::io::populate_from_str;
+
+    const NEEDS: &str = r#"{
+      "current_version": "head",
+      "versions": {
+        "base": { "needs": {
+          "NEED_1": { "id":"NEED_1","type":"need","links":[] },
+          "REQ_1": { "id":"REQ_1","type":"req","links":["NEED_1"] },
+          "REQ_2": { "id":"REQ_2","type":"req","links":[] },
+          "REQ_3": { "id":"REQ_3","type":"req","links":["NEED_1"] },
+          "SPEC_1": { "id":"SPEC_1","type":"spec","links":["REQ_1"],"docname":"spec/brakes","doctype":".rst" },
+          "TEST_1": { "id":"TEST_1","type":"test","links":["SPEC_1"] },
+          "TEST_2": { "id":"TEST_2","type":"test","links":["REQ_2"] },
+          "TEST_3": { "id":"TEST_3","type":"test","links":["REQ_3"] }
+        } },
+        "head": { "needs": {
+          "NEED_1": { "id":"NEED_1","type":"need","links":[] },
+          "REQ_1": { "id":"REQ_1","type":"req","title":"Changed","links":["NEED_1"] },
+          "REQ_3": { "id":"REQ_3","type":"req","links":["NEED_1"] },
+          "SPEC_1": { "id":"SPEC_1","type":"spec","links":["REQ_1"],"docname":"spec/brakes","doctype":".rst" },
+          "TEST_1": { "id":"TEST_1","type":"test","links":["SPEC_1"] },
+          "TEST_2": { "id":"TEST_2","type":"test","links":["REQ_2"] },
+          "TEST_3": { "id":"TEST_3","type":"test","links":["REQ_3"] }
+        } }
+      }
+    }"#;
+
+    fn summary(impact: &Impact) -> Vec<String> {
+        impact
+            .affected
+            .iter()
+            .map(|a| format!("{} {} {}", a.kind, a.distance, a.id))
+            .collect()
+    }
+
+    #[test]
+    fn affected_needs_by_kind_and_distance() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+
+        // REQ_3 shares the parent NEED_1 but is not affected, nor is its test
+        let impact = Impact::of(&g, &[NodeId::from("REQ_1")], &ImpactOptions::default()).unwrap();
+        assert_eq!(
+            summary(&impact),
+            vec!["need 1 NEED_1", "spec 1 SPEC_1", "test 2 TEST_1"]
+        );
+        let tests = &impact.by_kind()[&NodeKind::from("test")];
+        assert_eq!(tests[&2], vec![&NodeId::from("TEST_1")]);
+        assert_eq!(impact.documents(), BTreeSet::from(["spec/brakes.rst"]));
+
+        let downstream = ImpactOptions {
+            direction: Direction::Backward,
+            kinds: vec![NodeKind::from("test")],
+            ..Default::default()
+        };
+        let impact = Impact::of(&g, &[NodeId::from("REQ_1")], &downstream).unwrap();
+        assert_eq!(summary(&impact), vec!["test 2 TEST_1"]);
+
+        assert!(matches!(
+            Impact::of(&g, &[NodeId::from("NOPE")], &ImpactOptions::default()),
+            Err(Error::UnknownNeed { .. })
+        ));
+    }
+
+    #[test]
+    fn diff_seeds_include_removed_needs() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let base = ds.access_version("base").unwrap();
+        let head = ds.access_current_version().unwrap();
+        let diff = Diff::between(&base, &head, &DiffOptions::default());
+        let g = Graph::new(head).unwrap();
+
+        // REQ_1 was modified, REQ_2 removed while TEST_2 still links to it
+        let impact = Impact::of_diff(&g, &diff, &ImpactOptions::default());
+        assert_eq!(
+            impact.changed,
+            vec![NodeId::from("REQ_1"), NodeId::from("REQ_2")]
+        );
+        assert_eq!(
+            summary(&impact),
+            vec![
+                "need 1 NEED_1",
+                "spec 1 SPEC_1",
+                "test 1 TEST_2",
+                "test 2 TEST_1"
+            ]
+        );
+    }
+}
@@ -4,45 +4,81 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
-use crate::policy::Policies;
-use crate::representation::Dataset;
+use crate::baseline::Baseline;
+use crate::error::{Error, Result};
+use crate::policy::{Metamodel, Policies};
 use crate::representation::graph::Graph;
-use serde_json;
-use std::fs;
+use crate::representation::{Dataset, LinkType, NodeTypes, VersionAccessor};
+use crate::review::ReviewState;
+use crate::waiver::WaiverFile;
+use serde::de::DeserializeOwned;
 use std::path::Path;
-use std::path::PathBuf;
 
-pub fn populate_from_file(path: &Path) -> std::result::Result<Dataset, Box<dyn std::error::Error>> {
-    let raw = std::fs::read_to_string(path)?;
-    Ok(populate_from_str(&raw)?)
+pub fn populate_from_file(path: &Path) -> Result<Dataset> {
+    from_file(path)
 }
 
-pub fn populate_from_str(str: &str) -> std::result::Result<Dataset, serde_json::Error> {
-    let json: Dataset = serde_json::from_str(str)?;
-    Ok(json)
+pub fn populate_from_str(str: &str) -> Result<Dataset> {
+    serde_json::from_str(str).map_err(|e| Error::json(None, e))
 }
 
-pub fn load_graph_from_file(path: &Path) -> std::result::Result<Graph, Box<dyn std::error::Error>> {
+pub fn load_graph_from_file(path: &Path) -> Result<Graph> {
+    load_graph_from_file_with_link_types(path, &[])
+}
+
+pub fn load_graph_from_file_with_link_types(path: &Path, link_types: &[LinkType]) -> Result<Graph> {
     let loaded_dataset = populate_from_file(path)?;
-    let graph: Graph = Graph::new(loaded_dataset.access_current_version());
-    Ok(graph)
+    Graph::with_link_types(loaded_dataset.access_current_version()?, link_types)
+}
+
+// Graph built with what the policy configures - declared link types and need types
+pub fn load_graph_for_policy(path: &Path, policy: &Policies) -> Result<Graph> {
+    let loaded_dataset = populate_from_file(path)?;
+    graph_for_policy(loaded_dataset.access_current_version()?, policy)
+}
+
+// Same as `load_graph_for_policy` for any version of an already loaded export
+pub fn graph_for_policy(view: VersionAccessor<'_>, policy: &Policies) -> Result<Graph> {
+    Graph::with_types(view, &policy.link_types, NodeTypes::new(&policy.types))
+}
+
+pub fn load_policy_from_file(path: &Path) -> Result<Policies> {
+    from_file(path)
+}
+
+pub fn load_policy_from_str(str: &str) -> Result<Policies> {
+    serde_json::from_str(str).map_err(|e| Error::json(None, e))
+}
+
+pub fn load_metamodel_from_file(path: &Path) -> Result<Metamodel> {
+    from_file(path)
+}
+
+pub fn load_baseline_from_file(path: &Path) -> Result<Baseline> {
+    from_file(path)
+}
+
+pub fn load_waivers_from_file(path: &Path) -> Result<WaiverFile> {
+    from_file(path)
 }
 
-pub fn load_policy_from_file(
-    path: &Path,
-) -> std::result::Result<Policies, Box<dyn std::error::Error>> {
-    let raw = std::fs::read_to_string(path)?;
-    Ok(load_policy_from_str(&raw)?)
+pub fn load_review_state_from_file(path: &Path) -> Result<ReviewState> {
+    from_file(path)
 }
 
-pub fn load_policy_from_str(str: &str) -> std::result::Result<Policies, serde_json::Error> {
-    let json: Policies = serde_json::from_str(str)?;
-    Ok(json)
+fn from_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let raw = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&raw).map_err(|e| Error::json(Some(path.to_path_buf()), e))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use std::path::PathBuf;
 
     const TEST_JSON: &str = r#"
                                 {
@@ -91,11 +127,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_populate_from_str_invalid_json_position() {
+        let result = populate_from_str("{\n  \"current_version\": \"1.0\",\n  oops\n}");
+        assert!(matches!(
+            result,
+            Err(Error::Json {
+                path: None,
+                line: 3,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_populate_from_file_nonexistent() {
         let path = PathBuf::from("nonexistent.json");
         let result = populate_from_file(&path);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::Io { .. })));
     }
 
     #[test]
@@ -109,6 +158,17 @@ mod tests {
         fs::remove_file(temp_file).unwrap();
     }
 
+    #[test]
+    fn test_load_graph_from_file_invalid_json_reports_path() {
+        let temp_file = std::env::temp_dir().join("test_graph_invalid.json");
+        fs::write(&temp_file, "{ invalid json }").unwrap();
+
+        let result = load_graph_from_file(&temp_file);
+        assert!(matches!(result, Err(Error::Json { path: Some(ref p), .. }) if *p == temp_file));
+
+        fs::remove_file(temp_file).unwrap();
+    }
+
     #[test]
     fn test_load_graph_from_file_nonexistent() {
         let path = PathBuf::from("nonexistent_graph.json");
@@ -5,8 +5,12 @@
 // -----------------------------------------------------------------------------
 
 pub mod loader;
+pub mod writer;
 
 pub use loader::{
-    load_graph_from_file, load_policy_from_file, load_policy_from_str, populate_from_file,
+    graph_for_policy, load_baseline_from_file, load_graph_for_policy, load_graph_from_file,
+    load_graph_from_file_with_link_types, load_metamodel_from_file, load_policy_from_file,
+    load_policy_from_str, load_review_state_from_file, load_waivers_from_file, populate_from_file,
     populate_from_str,
 };
+pub use writer::{save_baseline_to_file, save_report_to_file, save_review_state_to_file};
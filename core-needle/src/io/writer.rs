@@ -0,0 +1,36 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::baseline::Baseline;
+use crate::error::{Error, Result};
+use crate::review::ReviewState;
+use serde::Serialize;
+use std::path::Path;
+
+pub fn save_baseline_to_file(path: &Path, baseline: &Baseline) -> Result<()> {
+    to_file(path, baseline)
+}
+
+pub fn save_review_state_to_file(path: &Path, state: &ReviewState) -> Result<()> {
+    to_file(path, state)
+}
+
+// Rendered reports, e.g. a traceability matrix
+pub fn save_report_to_file(path: &Path, report: &str) -> Result<()> {
+    std::fs::write(path, report).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn to_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let raw = serde_json::to_string_pretty(value)
+        .map_err(|e| Error::json(Some(path.to_path_buf()), e))?;
+    std::fs::write(path, raw + "\n").map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
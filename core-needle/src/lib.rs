@@ -4,6 +4,20 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+pub mod baseline;
+pub mod change;
+pub mod date;
+pub mod diff;
+pub mod error;
+pub mod filter;
+pub mod impact;
 pub mod io;
+pub mod matrix;
+pub mod pattern;
 pub mod policy;
 pub mod representation;
+pub mod review;
+pub mod subgraph;
+pub mod waiver;
+
+pub use error::{Error, Result};
@@ -0,0 +1,157 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::representation::{Direction, Graph, LinkType, NodeId, NodeKind};
+
+// Traceability matrix, e.g requirements to tests: every need of the source
+// type with the needs of the target types it reaches within `max_hops`.
+#[derive(Debug)]
+pub struct Matrix {
+    pub source: NodeKind,
+    pub targets: Vec<NodeKind>,
+    pub max_hops: Option<usize>,
+    pub rows: Vec<MatrixRow>,
+}
+
+#[derive(Debug)]
+pub struct MatrixRow {
+    pub source: NodeId,
+    pub title: Option<String>,
+    // Sorted by distance, then by id
+    pub reached: Vec<Reached>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reached {
+    pub target: NodeId,
+    // Column the target is listed under, the first target type it is a kind of
+    pub kind: NodeKind,
+    // Length of the shortest path, `1` for a direct link
+    pub distance: usize,
+}
+
+// What to follow while building the matrix, `max_hops` of `1` means direct
+// links only and `None` no limit.
+#[derive(Debug, Default, Clone)]
+pub struct MatrixOptions {
+    pub max_hops: Option<usize>,
+    pub link_types: Vec<LinkType>,
+    pub direction: Direction,
+}
+
+impl Matrix {
+    pub fn build(
+        g: &Graph,
+        source: &NodeKind,
+        targets: &[NodeKind],
+        options: &MatrixOptions,
+    ) -> Self {
+        let rows = g
+            .of_type(source)
+            .into_iter()
+            .map(|id| {
+                let reached = g
+                    .reachable(
+                        &[id],
+                        &options.link_types,
+                        options.direction,
+                        options.max_hops,
+                    )
+                    .into_iter()
+                    .filter_map(|(target, distance)| {
+                        let kind = targets.iter().find(|k| g.is_a(target, k))?;
+                        Some(Reached {
+                            target: target.clone(),
+                            kind: kind.clone(),
+                            distance,
+                        })
+                    })
+                    .collect();
+                MatrixRow {
+                    source: id.clone(),
+                    title: g.nodes.get(id).and_then(|n| n.title.clone()),
+                    reached,
+                }
+            })
+            .collect();
+
+        Self {
+            source: source.clone(),
+            targets: targets.to_vec(),
+            max_hops: options.max_hops,
+            rows,
+        }
+    }
+
+    pub fn uncovered(&self) -> usize {
+        self.rows.iter().filter(|r| !r.covered()).count()
+    }
+}
+
+impl MatrixRow {
+    pub fn covered(&self) -> bool {
+        !self.reached.is_empty()
+    }
+
+    pub fn of_kind<'a>(&'a self, kind: &'a NodeKind) -> impl Iterator<Item = &'a Reached> {
+        self.reached.iter().filter(move |r| &r.kind == kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+
+    #[test]
+    fn rows_list_reached_targets_with_distance() {
+        let ds = populate_from_str(
+            r#"{ "current_version": "1.0", "versions": { "1.0": { "needs": {
+                "REQ_1": { "id":"REQ_1","type":"req","title":"Braking","links":["SPEC_1","TEST_1"] },
+                "REQ_2": { "id":"REQ_2","type":"req","links":[] },
+                "SPEC_1": { "id":"SPEC_1","type":"spec","links":["RUN_1"] },
+                "TEST_1": { "id":"TEST_1","type":"test","links":[] },
+                "RUN_1": { "id":"RUN_1","type":"testrun","links":[] }
+            } } } }"#,
+        )
+        .unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let targets = [NodeKind::from("test"), NodeKind::from("testrun")];
+
+        let direct = Matrix::build(
+            &g,
+            &NodeKind::from("req"),
+            &targets,
+            &MatrixOptions {
+                max_hops: Some(1),
+                ..Default::default()
+            },
+        );
+        assert_eq!(direct.rows.len(), 2);
+        assert_eq!(direct.rows[0].title.as_deref(), Some("Braking"));
+        assert_eq!(
+            direct.rows[0].reached,
+            vec![Reached {
+                target: NodeId::from("TEST_1"),
+                kind: NodeKind::from("test"),
+                distance: 1,
+            }]
+        );
+        assert_eq!(direct.uncovered(), 1);
+
+        let transitive = Matrix::build(
+            &g,
+            &NodeKind::from("req"),
+            &targets,
+            &MatrixOptions::default(),
+        );
+        let run: Vec<_> = transitive.rows[0]
+            .of_kind(&targets[1])
+            .map(|r| (r.target.to_string(), r.distance))
+            .collect();
+        assert_eq!(run, vec![("RUN_1".to_string(), 2)]);
+    }
+}
@@ -0,0 +1,99 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+
+// Regular expression compiled once when the policy is read, an invalid one is
+// reported as a policy load error. Matches anywhere unless anchored.
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn new(raw: &str) -> Result<Self, regex::Error> {
+        Regex::new(raw).map(Self)
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(d)?;
+        Self::new(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+// Shell like wildcard match, `*` is any run of characters (also empty) and `?`
+// exactly one. Everything else is compared literally.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+
+    let (mut pi, mut ti) = (0, 0);
+    // Position of the last `*` and the text index it was tried at
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        match p.get(pi) {
+            Some('*') => {
+                star = Some((pi, ti));
+                pi += 1;
+            }
+            Some(&c) if c == '?' || c == t[ti] => {
+                pi += 1;
+                ti += 1;
+            }
+            _ => match star {
+                // Let the last `*` swallow one more character
+                Some((sp, st)) => {
+                    pi = sp + 1;
+                    ti = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(glob_match("IMPL_*", "IMPL_LEGACY_1"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("REQ_00?", "REQ_001"));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(glob_match("adas/*.rst", "adas/index.rst"));
+        assert!(!glob_match("REQ_00?", "REQ_0011"));
+        assert!(!glob_match("IMPL_*", "REQ_1"));
+        assert!(!glob_match("a*b", "aXXc"));
+        assert!(glob_match("IMPL_1", "IMPL_1"));
+    }
+
+    #[test]
+    fn invalid_regex_fails_to_deserialize() {
+        let ok: Pattern = serde_json::from_str(r#""^REQ_[0-9]+$""#).unwrap();
+        assert!(ok.is_match("REQ_12"));
+        assert!(!ok.is_match("REQ_X"));
+        assert!(serde_json::from_str::<Pattern>(r#""REQ_(""#).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::Result;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Graph, Issue, IssueCode, LinkType, NodeId, Severity};
+use serde::Deserialize;
+
+pub const PARAMS: &[ParamSpec] = &[ParamSpec::required("allowed", ParamType::LinkTypes)];
+
+// Selected needs may only link through the `allowed` link types
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    allowed: Vec<LinkType>,
+}
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
+
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("ALLOWED_LINK_TYPES");
+    let msg = rule.message.as_deref().unwrap_or("link type not allowed");
+
+    let mut out = Vec::new();
+    for source in selected.iter().copied() {
+        for edge in g.out_edges(source) {
+            if params.allowed.contains(&edge.link_type) {
+                continue;
+            }
+            out.push(Issue {
+                severity: sev,
+                code: IssueCode::from_rule_code(code),
+                subject: source.clone(),
+                detail: format!("{msg}: {source} -[{}]-> {}", edge.link_type, edge.target),
+                related: vec![edge.target.clone()],
+                key: None,
+            });
+        }
+    }
+    Ok(out)
+}
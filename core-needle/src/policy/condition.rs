@@ -0,0 +1,94 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::Result;
+use crate::filter::Filter;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Graph, Issue, IssueCode, NodeId, Severity};
+use serde::Deserialize;
+
+pub const PARAMS: &[ParamSpec] = &[ParamSpec::required("filter", ParamType::Filter)];
+
+// Every selected need has to satisfy the filter string, e.g.
+// `status in ['approved', 'implemented']`
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    filter: Filter,
+}
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
+
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("CONDITION");
+    let detail = match rule.message.as_deref() {
+        Some(msg) => msg.to_string(),
+        None => format!("condition `{}` not met", params.filter.as_str()),
+    };
+
+    let mut out = Vec::new();
+    for id in selected.iter().copied() {
+        if !params.filter.matches(&g.nodes[id]) {
+            out.push(Issue {
+                severity: sev,
+                code: IssueCode::from_rule_code(code),
+                subject: id.clone(),
+                detail: detail.clone(),
+                related: Vec::new(),
+                key: None,
+            });
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::{Graph, NodeId};
+
+    #[test]
+    fn selected_needs_must_satisfy_the_filter() {
+        let ds = populate_from_str(
+            r#"{
+              "current_version": "1.0",
+              "versions": { "1.0": { "needs": {
+                "REQ_1": { "id":"REQ_1","type":"req","status":"approved","tags":["safety"] },
+                "REQ_2": { "id":"REQ_2","type":"req","status":"draft","tags":["safety"] },
+                "REQ_3": { "id":"REQ_3","type":"req","status":"draft" }
+              } } }
+            }"#,
+        )
+        .unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [
+                { "rule_id": "condition",
+                  "selector": { "filter": "type == 'req' and 'safety' in tags" },
+                  "params": { "filter": "status in ['approved', 'implemented']" } }
+            ] }"#,
+        )
+        .unwrap();
+
+        let issues = evaluate(&g, &policy).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].subject, NodeId::from("REQ_2"));
+        assert_eq!(
+            issues[0].detail,
+            "condition `status in ['approved', 'implemented']` not met"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::{Error, Result};
+use crate::policy::model::{Defaults, Rule, Selector};
+use crate::policy::registry::Registry;
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{
+    Direction, Graph, Issue, IssueCode, LinkType, NodeId, PROJECT_SUBJECT, Severity,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::required("metric", ParamType::Metric),
+    ParamSpec::optional("rule", ParamType::Rule),
+    ParamSpec::optional("min", ParamType::Number),
+    ParamSpec::optional("max", ParamType::Number),
+    ParamSpec::optional("link_types", ParamType::LinkTypes),
+    ParamSpec::optional("direction", ParamType::Direction),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    // Number of selected needs
+    Count,
+    // Share of selected needs without an issue from the inner `rule`
+    Ratio,
+    // Average number of links per selected need
+    FanOut,
+}
+
+impl MetricKind {
+    pub const ALL: &[&str] = &["count", "ratio", "fan_out"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Ratio => "ratio",
+            Self::FanOut => "fan_out",
+        }
+    }
+}
+
+// Value measured over the selected needs, kept in the rule report whether
+// the threshold holds or not
+#[derive(Debug, Clone, Serialize)]
+pub struct Metric {
+    pub metric: MetricKind,
+    pub value: f64,
+    pub selected: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passing: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    // Needs which failed the inner rule
+    #[serde(skip)]
+    pub failing: Vec<NodeId>,
+}
+
+impl Metric {
+    // e.g `ratio is 0.83 (10 of 12 needs pass)`
+    pub fn describe(&self) -> String {
+        let value = number(self.value);
+        match (self.passing, self.links) {
+            (Some(passing), _) => format!(
+                "{} is {value} ({passing} of {} needs pass)",
+                self.metric.as_str(),
+                self.selected
+            ),
+            (_, Some(links)) => format!(
+                "{} is {value} ({links} links over {} needs)",
+                self.metric.as_str(),
+                self.selected
+            ),
+            _ => format!("{} is {value}", self.metric.as_str()),
+        }
+    }
+
+    pub fn below_min(&self) -> bool {
+        self.min.is_some_and(|min| self.value < min)
+    }
+
+    pub fn above_max(&self) -> bool {
+        self.max.is_some_and(|max| self.value > max)
+    }
+}
+
+// Whole numbers without decimals, anything else rounded to two
+fn number(v: f64) -> String {
+    if v.fract() == 0.0 {
+        format!("{v:.0}")
+    } else {
+        format!("{v:.2}")
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    metric: MetricKind,
+    #[serde(default)]
+    rule: Option<InnerRule>,
+    #[serde(default)]
+    min: Option<f64>,
+    #[serde(default)]
+    max: Option<f64>,
+    #[serde(default)]
+    link_types: Vec<LinkType>,
+    #[serde(default)]
+    direction: Direction,
+}
+
+// Rule evaluated on the selected needs for `ratio`, without a selector of its own
+#[derive(Deserialize)]
+struct InnerRule {
+    rule_id: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+pub fn measure(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Metric> {
+    let params: Params = rule.params()?;
+    let total = selected.len();
+    let mut metric = Metric {
+        metric: params.metric,
+        value: total as f64,
+        selected: total,
+        passing: None,
+        links: None,
+        min: params.min,
+        max: params.max,
+        failing: Vec::new(),
+    };
+
+    match params.metric {
+        MetricKind::Count => {}
+        MetricKind::Ratio => {
+            let failing = failing(g, rule, params.rule, selected, defaults)?;
+            let passing = total - failing.len();
+            // Nothing selected - nothing is missing
+            metric.value = if total == 0 {
+                1.0
+            } else {
+                passing as f64 / total as f64
+            };
+            metric.passing = Some(passing);
+            metric.failing = failing;
+        }
+        MetricKind::FanOut => {
+            let links: usize = selected
+                .iter()
+                .map(|id| g.via(id, &params.link_types, params.direction).len())
+                .sum();
+            metric.value = if total == 0 {
+                0.0
+            } else {
+                links as f64 / total as f64
+            };
+            metric.links = Some(links);
+        }
+    }
+    Ok(metric)
+}
+
+fn failing(
+    g: &Graph,
+    rule: &Rule,
+    inner: Option<InnerRule>,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<NodeId>> {
+    let invalid = |reason: String| Error::InvalidRuleParams {
+        rule_id: rule.rule_id.clone(),
+        reason,
+    };
+    let inner = inner.ok_or_else(|| invalid("`ratio` needs an inner `rule`".to_string()))?;
+    let reg = Registry::builtins();
+    let run = reg
+        .get(&inner.rule_id)
+        .ok_or_else(|| invalid(format!("rule `{}` is not registered", inner.rule_id)))?;
+
+    let inner = Rule {
+        rule_id: inner.rule_id,
+        selector: Selector::default(),
+        params: inner.params,
+        severity: None,
+        code: None,
+        message: None,
+    };
+    let within: BTreeSet<&NodeId> = selected.iter().copied().collect();
+    let failing: BTreeSet<NodeId> = run(g, &inner, selected, defaults)?
+        .into_iter()
+        .map(|i| i.subject)
+        .filter(|id| within.contains(id))
+        .collect();
+    Ok(failing.into_iter().collect())
+}
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let metric = measure(g, rule, selected, defaults)?;
+    Ok(issues(&metric, rule, defaults))
+}
+
+// Single project issue when the measured value is out of bounds
+pub fn issues(metric: &Metric, rule: &Rule, defaults: &Defaults) -> Vec<Issue> {
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("COVERAGE");
+    let msg = rule
+        .message
+        .as_deref()
+        .unwrap_or("coverage threshold not met");
+
+    let threshold = match (metric.below_min(), metric.above_max()) {
+        (true, _) => format!(
+            "below the minimum {}",
+            number(metric.min.unwrap_or_default())
+        ),
+        (_, true) => format!(
+            "above the maximum {}",
+            number(metric.max.unwrap_or_default())
+        ),
+        _ => return Vec::new(),
+    };
+    vec![Issue {
+        severity: sev,
+        code: IssueCode::from_rule_code(code),
+        subject: NodeId::from(PROJECT_SUBJECT),
+        detail: format!("{msg}: {threshold}"),
+        related: metric.failing.clone(),
+        key: None,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate_rules;
+    use crate::representation::{Graph, NodeId};
+
+    const NEEDS: &str = r#"{
+      "current_version": "1.0",
+      "versions": { "1.0": { "needs": {
+        "SWREQ_1": { "id":"SWREQ_1","type":"swreq","links":["TEST_1"] },
+        "SWREQ_2": { "id":"SWREQ_2","type":"swreq","links":["TEST_1","TEST_2"] },
+        "SWREQ_3": { "id":"SWREQ_3","type":"swreq","links":[] },
+        "TEST_1": { "id":"TEST_1","type":"test","links":[] },
+        "TEST_2": { "id":"TEST_2","type":"test","links":[] }
+      } } }
+    }"#;
+
+    fn run(params: &str) -> (Vec<String>, serde_json::Value) {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(&format!(
+            r#"{{ "version": 1, "rules": [ {{ "rule_id": "coverage",
+                 "selector": {{ "kinds": ["swreq"] }}, "params": {params} }} ] }}"#
+        ))
+        .unwrap();
+        let reports = evaluate_rules(&g, &policy).unwrap();
+        let report = &reports[0];
+        (
+            report.issues.iter().map(|i| i.detail.clone()).collect(),
+            serde_json::to_value(report.metric.as_ref().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn ratio_of_needs_passing_the_inner_rule() {
+        let (details, metric) = run(r#"{ "metric": "ratio", "min": 0.95,
+                 "rule": { "rule_id": "reach_kind", "params": { "target_kinds": ["test"] } } }"#);
+        assert_eq!(
+            details,
+            vec!["coverage threshold not met: below the minimum 0.95"]
+        );
+        assert_eq!(metric["passing"], 2);
+        assert_eq!(metric["selected"], 3);
+
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [ { "rule_id": "coverage", "selector": { "kinds": ["swreq"] },
+                 "params": { "metric": "ratio", "min": 0.5,
+                             "rule": { "rule_id": "has_outgoing" } } } ] }"#,
+        )
+        .unwrap();
+        let reports = evaluate_rules(&g, &policy).unwrap();
+        assert!(reports[0].issues.is_empty());
+        assert_eq!(
+            reports[0].metric.as_ref().unwrap().failing,
+            vec![NodeId::from("SWREQ_3")]
+        );
+    }
+
+    #[test]
+    fn count_and_fan_out() {
+        let (details, metric) = run(r#"{ "metric": "count", "max": 2 }"#);
+        assert_eq!(
+            details,
+            vec!["coverage threshold not met: above the maximum 2"]
+        );
+        assert_eq!(metric["value"], 3.0);
+
+        let (details, metric) = run(r#"{ "metric": "fan_out", "min": 1 }"#);
+        assert!(details.is_empty());
+        assert_eq!(metric["links"], 3);
+        assert_eq!(metric["value"], 1.0);
+    }
+}
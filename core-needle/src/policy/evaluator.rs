@@ -4,20 +4,127 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
-use crate::policy::model::Policies;
+use crate::error::Result;
+use crate::policy::coverage::Metric;
+use crate::policy::model::{Policies, Rule};
 use crate::policy::registry::Registry;
+use crate::policy::validation::validate_with;
 use crate::representation::Issue;
 use crate::representation::graph::Graph;
+use std::collections::BTreeSet;
 
-pub fn evaluate(g: &Graph, p: &Policies) -> Vec<Issue> {
+// Issues produced by a single policy rule, `index` is its position in `Policies::rules`.
+#[derive(Debug)]
+pub struct RuleReport<'p> {
+    pub index: usize,
+    pub rule: &'p Rule,
+    pub issues: Vec<Issue>,
+    // Raw value of aggregate rules, e.g. the coverage ratio
+    pub metric: Option<Metric>,
+}
+
+pub fn evaluate(g: &Graph, p: &Policies) -> Result<Vec<Issue>> {
+    Ok(evaluate_rules(g, p)?
+        .into_iter()
+        .flat_map(|r| r.issues)
+        .collect())
+}
+
+// Every issue with the code it's reported under - the issue code for issues
+// found while building the graph, the rule code for policy issues
+pub fn coded_issues<'a>(
+    graph_issues: &'a [Issue],
+    reports: &'a [RuleReport<'_>],
+) -> impl Iterator<Item = (&'a str, &'a Issue)> {
+    graph_issues
+        .iter()
+        .map(|issue| (issue.code.to_str(), issue))
+        .chain(reports.iter().flat_map(|r| {
+            let code = r.rule.code_or_id();
+            r.issues.iter().map(move |issue| (code, issue))
+        }))
+}
+
+// Keeps the issues `keep` accepts, with the codes of `coded_issues`
+pub fn retain_issues(
+    graph_issues: &mut Vec<Issue>,
+    reports: &mut [RuleReport<'_>],
+    mut keep: impl FnMut(&str, &Issue) -> bool,
+) {
+    graph_issues.retain(|issue| keep(issue.code.to_str(), issue));
+    for report in reports.iter_mut() {
+        let code = report.rule.code_or_id();
+        report.issues.retain(|issue| keep(code, issue));
+    }
+}
+
+pub fn evaluate_rules<'p>(g: &Graph, p: &'p Policies) -> Result<Vec<RuleReport<'p>>> {
     let reg = Registry::builtins();
+    // Rules whose params can't be used are reported as diagnostics and
+    // skipped, in strict mode the run stops before evaluating anything
+    let skipped: BTreeSet<usize> = validate_with(p, &reg, &g.types)
+        .into_iter()
+        .filter(|d| d.kind.skips_rule())
+        .map(|d| d.rule)
+        .collect();
     let mut out = Vec::new();
-    for rule in &p.rules {
+    for (index, rule) in p.rules.iter().enumerate() {
+        if skipped.contains(&index) {
+            continue;
+        }
         if let Some(run) = reg.get(&rule.rule_id) {
-            out.extend(run(g, rule, &p.defaults));
+            let selected = rule.selector.select(g);
+            // Aggregate rules are measured once, their issues follow from the value
+            let (issues, metric) = match reg.aggregate(&rule.rule_id) {
+                Some((measure, issues)) => {
+                    let metric = measure(g, rule, &selected, &p.defaults)?;
+                    (issues(&metric, rule, &p.defaults), Some(metric))
+                }
+                None => (run(g, rule, &selected, &p.defaults)?, None),
+            };
+            out.push(RuleReport {
+                index,
+                rule,
+                issues,
+                metric,
+            });
         } else {
-            // TODO handle unknown rule - for now just ignore it.
+            // Unknown rules are reported up front by `policy::validate`.
         }
     }
-    out
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{load_policy_from_str, populate_from_str};
+
+    #[test]
+    fn rules_with_unusable_params_are_skipped() {
+        let ds = populate_from_str(
+            r#"{ "current_version": "1.0", "versions": { "1.0": { "needs": {
+                "REQ_1": { "id":"REQ_1","type":"req","links":[] }
+            } } } }"#,
+        )
+        .unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let p = load_policy_from_str(
+            r#"{ "version": 1, "rules": [
+                { "rule_id": "has_outgoing", "selector": { "kinds": ["req"] }, "params": { "min": "1" } },
+                { "rule_id": "condition", "selector": { "kinds": ["req"] }, "params": { "filter": "status ==" } },
+                { "rule_id": "has_outgoing", "selector": { "kinds": ["req"] }, "params": { "mni": 1 } },
+                { "rule_id": "coverage", "selector": {}, "params": { "metric": "ratio", "min": 0.9 } },
+                { "rule_id": "coverage", "selector": {}, "params": { "metric": "ratio", "min": 0.9,
+                  "rule": { "rule_id": "reach_kind", "params": {} } } }
+            ] }"#,
+        )
+        .unwrap();
+
+        // An unknown param is only a warning, the rule still runs
+        let reports = evaluate_rules(&g, &p).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].index, 2);
+        assert_eq!(reports[0].issues.len(), 1);
+    }
 }
@@ -4,19 +4,27 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+use crate::error::Result;
 use crate::policy::model::{Defaults, Rule};
-use crate::representation::{Graph, Issue, IssueCode, Severity};
+use crate::representation::{Graph, Issue, IssueCode, NodeId, Severity};
 
+use crate::policy::validation::{ParamSpec, ParamType};
 use serde::Deserialize;
 
+pub const PARAMS: &[ParamSpec] = &[ParamSpec::required("field", ParamType::String)];
+
 #[derive(Deserialize)]
-struct Params {
+pub(crate) struct Params {
     field: String,
 }
 
-pub fn run(g: &Graph, rule: &Rule, defaults: &Defaults) -> Vec<Issue> {
-    let params: Params =
-        serde_json::from_value(rule.params.clone()).expect("field_present requires { field }");
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
 
     let sev = rule
         .severity
@@ -29,18 +37,18 @@ pub fn run(g: &Graph, rule: &Rule, defaults: &Defaults) -> Vec<Issue> {
     let msg = rule.message.as_deref().unwrap_or("required field missing");
 
     let mut out = Vec::new();
-    for kind in &rule.selector.kinds {
-        for id in g.of_kind(*kind) {
-            let n = &g.nodes[id];
-            if !n.field_present(&params.field) {
-                out.push(Issue {
-                    severity: sev,
-                    code,
-                    subject: id.clone(),
-                    detail: msg.to_string(),
-                });
-            }
+    for id in selected.iter().copied() {
+        let n = &g.nodes[id];
+        if !n.field_present(&params.field) {
+            out.push(Issue {
+                severity: sev,
+                code: code.clone(),
+                subject: id.clone(),
+                detail: msg.to_string(),
+                related: Vec::new(),
+                key: None,
+            });
         }
     }
-    out
+    Ok(out)
 }
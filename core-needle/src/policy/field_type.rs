@@ -0,0 +1,184 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::date::Date;
+use crate::error::Result;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Graph, Issue, IssueCode, NodeId, Severity};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::required("field", ParamType::String),
+    ParamSpec::required("type", ParamType::FieldType),
+    ParamSpec::optional("values", ParamType::Strings),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    // One of `values`, for list options every item
+    Enum,
+    // `YYYY-MM-DD`
+    Date,
+    Int,
+}
+
+impl FieldType {
+    pub const ALL: &[&str] = &["string", "enum", "date", "int"];
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    field: String,
+    #[serde(rename = "type")]
+    ty: FieldType,
+    #[serde(default)]
+    values: Vec<String>,
+}
+
+// Checks the value of a need option when it's set, use `field_present` to
+// require it. Sphinx-Needs exports every option as a string, so numbers and
+// dates are accepted in their string form too.
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
+
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("FIELD_TYPE");
+
+    let mut out = Vec::new();
+    for id in selected.iter().copied() {
+        let Some(value) = g.nodes[id].field_value(&params.field) else {
+            continue;
+        };
+        if let Some(problem) = check(&params, &value) {
+            out.push(Issue {
+                severity: sev,
+                code: IssueCode::from_rule_code(code),
+                subject: id.clone(),
+                detail: match rule.message.as_deref() {
+                    Some(msg) => msg.to_string(),
+                    None => format!("`{}` {problem}", params.field),
+                },
+                related: Vec::new(),
+                key: None,
+            });
+        }
+    }
+    Ok(out)
+}
+
+fn check(params: &Params, value: &Value) -> Option<String> {
+    match (params.ty, value) {
+        (_, Value::Null) => None,
+        (_, Value::String(s)) if s.trim().is_empty() => None,
+        (FieldType::String, Value::String(_)) => None,
+        (FieldType::Int, Value::Number(n)) if n.is_i64() || n.is_u64() => None,
+        (FieldType::Int, Value::String(s)) if s.trim().parse::<i64>().is_ok() => None,
+        (FieldType::Date, Value::String(s)) if Date::parse(s).is_some() => None,
+        (FieldType::Enum, Value::String(s)) if params.values.contains(s) => None,
+        (FieldType::Enum, Value::Array(items)) => items
+            .iter()
+            .find(|v| {
+                !v.as_str()
+                    .is_some_and(|s| params.values.contains(&s.to_string()))
+            })
+            .map(|bad| {
+                format!(
+                    "has `{}`, expected one of {}",
+                    scalar(bad),
+                    params.values.join(", ")
+                )
+            }),
+        (FieldType::Enum, other) => Some(format!(
+            "is `{}`, expected one of {}",
+            scalar(other),
+            params.values.join(", ")
+        )),
+        (FieldType::String, other) => Some(format!("is `{}`, expected a string", scalar(other))),
+        (FieldType::Int, other) => Some(format!("is `{}`, expected an integer", scalar(other))),
+        (FieldType::Date, other) => Some(format!(
+            "is `{}`, expected a date (YYYY-MM-DD)",
+            scalar(other)
+        )),
+    }
+}
+
+fn scalar(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::Graph;
+
+    #[test]
+    fn values_are_checked_against_their_type() {
+        let ds = populate_from_str(
+            r#"{
+              "current_version": "1.0",
+              "versions": { "1.0": { "needs": {
+                "REQ_1": { "id":"REQ_1","type":"req","asil":"B","due":"2025-03-01","effort":"5" },
+                "REQ_2": { "id":"REQ_2","type":"req","asil":"E","due":"next week","effort":3 },
+                "REQ_3": { "id":"REQ_3","type":"req","asil":"","effort":"a lot" }
+              } } }
+            }"#,
+        )
+        .unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [
+                { "rule_id": "field_type", "selector": { "kinds": ["req"] },
+                  "params": { "field": "asil", "type": "enum", "values": ["QM", "A", "B", "C", "D"] } },
+                { "rule_id": "field_type", "selector": { "kinds": ["req"] },
+                  "params": { "field": "due", "type": "date" } },
+                { "rule_id": "field_type", "selector": { "kinds": ["req"] },
+                  "params": { "field": "effort", "type": "int" } }
+            ] }"#,
+        )
+        .unwrap();
+
+        let mut found: Vec<(String, String)> = evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .map(|i| (i.subject.to_string(), i.detail))
+            .collect();
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "REQ_2".to_string(),
+                    "`asil` is `E`, expected one of QM, A, B, C, D".to_string()
+                ),
+                (
+                    "REQ_2".to_string(),
+                    "`due` is `next week`, expected a date (YYYY-MM-DD)".to_string()
+                ),
+                (
+                    "REQ_3".to_string(),
+                    "`effort` is `a lot`, expected an integer".to_string()
+                ),
+            ]
+        );
+    }
+}
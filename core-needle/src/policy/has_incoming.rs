@@ -0,0 +1,71 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::Result;
+use crate::policy::has_outgoing::count_links;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::ParamSpec;
+use crate::representation::{Direction, Graph, Issue, NodeId};
+
+// Same params as `has_outgoing`, counted over the links pointing to the need
+pub const PARAMS: &[ParamSpec] = crate::policy::has_outgoing::PARAMS;
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    count_links(g, rule, selected, defaults, Direction::Backward)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::Graph;
+
+    const NEEDS: &str = r#"{
+      "current_version": "1.0",
+      "versions": { "1.0": { "needs": {
+        "REQ_1": { "id":"REQ_1","type":"req","links":["TEST_1"] },
+        "REQ_2": { "id":"REQ_2","type":"req","links":["TEST_1","TEST_2"] },
+        "IMPL_1": { "id":"IMPL_1","type":"impl","links":["TEST_2"] },
+        "TEST_1": { "id":"TEST_1","type":"test","links":[] },
+        "TEST_2": { "id":"TEST_2","type":"test","links":[] },
+        "TEST_3": { "id":"TEST_3","type":"test","links":["REQ_1"] }
+      } } }
+    }"#;
+
+    fn subjects(policy: &str) -> Vec<(String, String)> {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(policy).unwrap();
+        let mut out: Vec<(String, String)> = evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .map(|i| (i.subject.to_string(), i.detail))
+            .collect();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn incoming_links_are_counted() {
+        let found = subjects(
+            r#"{ "version": 1, "rules": [ { "rule_id": "has_incoming", "selector": { "kinds": ["test"] },
+                 "params": { "max": 1 } } ] }"#,
+        );
+        assert_eq!(
+            found,
+            vec![
+                ("TEST_1".into(), "too many backward links".into()),
+                ("TEST_2".into(), "too many backward links".into()),
+                ("TEST_3".into(), "missing required backward links".into()),
+            ]
+        );
+    }
+}
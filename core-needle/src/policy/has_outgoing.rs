@@ -4,40 +4,87 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+use crate::error::Result;
 use crate::policy::model::{Defaults, Rule};
-use crate::representation::{Graph, Issue, IssueCode, Severity};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Direction, Graph, Issue, IssueCode, LinkType, NodeId, Severity};
 use serde::Deserialize;
 
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::optional("min", ParamType::Usize),
+    ParamSpec::optional("max", ParamType::Usize),
+    ParamSpec::optional("link_types", ParamType::LinkTypes),
+];
+
 #[derive(Deserialize)]
-struct Params {
+pub(crate) struct Params {
+    #[serde(default = "default_min")]
     min: usize,
+    #[serde(default)]
+    max: Option<usize>,
+    #[serde(default)]
+    link_types: Vec<LinkType>,
+}
+
+fn default_min() -> usize {
+    1
+}
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    count_links(g, rule, selected, defaults, Direction::Forward)
 }
 
-pub fn run(g: &Graph, rule: &Rule, defaults: &Defaults) -> Vec<Issue> {
-    let params: Params = serde_json::from_value(rule.params.clone()).unwrap_or(Params { min: 1 });
+// Shared with `has_incoming`, which counts the links pointing to the need
+pub(crate) fn count_links(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+    direction: Direction,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
 
     let sev = rule
         .severity
         .or(defaults.severity)
         .unwrap_or(Severity::Error);
-    let code = rule.code.as_deref().unwrap_or("HAS_OUTGOING");
-    let msg = rule
-        .message
-        .as_deref()
-        .unwrap_or("missing required forward links");
+    let (code, missing, too_many) = match direction {
+        Direction::Backward => (
+            "HAS_INCOMING",
+            "missing required backward links",
+            "too many backward links",
+        ),
+        _ => (
+            "HAS_OUTGOING",
+            "missing required forward links",
+            "too many forward links",
+        ),
+    };
+    let code = rule.code.as_deref().unwrap_or(code);
 
     let mut out = Vec::new();
-    for kind in &rule.selector.kinds {
-        for id in g.of_kind(*kind) {
-            if g.out(id).len() < params.min {
-                out.push(Issue {
-                    severity: sev,
-                    code: IssueCode::from_rule_code(code),
-                    subject: id.clone(),
-                    detail: msg.to_string(),
-                });
-            }
-        }
+    for id in selected.iter().copied() {
+        let count = g.via(id, &params.link_types, direction).len();
+        let detail = if count < params.min {
+            rule.message.as_deref().unwrap_or(missing)
+        } else if params.max.is_some_and(|max| count > max) {
+            rule.message.as_deref().unwrap_or(too_many)
+        } else {
+            continue;
+        };
+        out.push(Issue {
+            severity: sev,
+            code: IssueCode::from_rule_code(code),
+            subject: id.clone(),
+            detail: detail.to_string(),
+            related: Vec::new(),
+            key: None,
+        });
     }
-    out
+    Ok(out)
 }
@@ -0,0 +1,194 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::Result;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Graph, Issue, IssueCode, LinkType, NodeId, NodeKind, Severity};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::optional("allowed", ParamType::KindMap),
+    ParamSpec::optional("forbidden", ParamType::KindMap),
+    ParamSpec::optional("link_types", ParamType::LinkTypes),
+];
+
+// Per source kind: `allowed` lists the only kinds it may link to, `forbidden`
+// the kinds it must never link to. Source kinds not listed are not checked.
+// Types follow the hierarchy - a source uses the entry of its nearest listed
+// ancestor and a target matches any listed ancestor.
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    #[serde(default)]
+    allowed: HashMap<NodeKind, Vec<NodeKind>>,
+    #[serde(default)]
+    forbidden: HashMap<NodeKind, Vec<NodeKind>>,
+    #[serde(default)]
+    link_types: Vec<LinkType>,
+}
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
+    // Keys are looked up by the canonical type of a need, like `Graph::of_kind`
+    let resolve = |map: HashMap<NodeKind, Vec<NodeKind>>| {
+        let mut out: HashMap<NodeKind, Vec<NodeKind>> = HashMap::new();
+        for (kind, targets) in map {
+            out.entry(g.types.resolve(kind.as_str()))
+                .or_default()
+                .extend(targets);
+        }
+        out
+    };
+    let allowed_by_kind = resolve(params.allowed);
+    let forbidden_by_kind = resolve(params.forbidden);
+
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("LINK_TARGETS");
+    let msg = rule
+        .message
+        .as_deref()
+        .unwrap_or("link target not permitted");
+
+    let nearest = |map: &'_ HashMap<NodeKind, Vec<NodeKind>>, kind: &NodeKind| {
+        g.types
+            .lineage(kind)
+            .iter()
+            .find_map(|k| map.get(k))
+            .cloned()
+    };
+    let any_of = |kind: &NodeKind, kinds: &[NodeKind]| kinds.iter().any(|k| g.types.is_a(kind, k));
+
+    let mut out = Vec::new();
+    for source in selected.iter().copied() {
+        let Some(source_kind) = g.kind_of(source) else {
+            continue;
+        };
+        let allowed = nearest(&allowed_by_kind, &source_kind);
+        let forbidden = nearest(&forbidden_by_kind, &source_kind);
+        if allowed.is_none() && forbidden.is_none() {
+            continue;
+        }
+
+        for target in g.out_via(source, &params.link_types) {
+            // Links to needs which don't exist are reported while building the graph
+            let Some(target_kind) = g.kind_of(target) else {
+                continue;
+            };
+            let offending = allowed.as_ref().is_some_and(|a| !any_of(&target_kind, a))
+                || forbidden.as_ref().is_some_and(|f| any_of(&target_kind, f));
+            if offending {
+                out.push(Issue {
+                    severity: sev,
+                    code: IssueCode::from_rule_code(code),
+                    subject: source.clone(),
+                    detail: format!("{msg}: {source} ({source_kind}) -> {target} ({target_kind})"),
+                    related: vec![target.clone()],
+                    key: None,
+                });
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::{Graph, IssueCode, NodeId, NodeTypes};
+
+    #[test]
+    fn offending_edges_are_reported_with_both_endpoints() {
+        let ds = populate_from_str(
+            r#"{
+              "current_version": "1.0",
+              "versions": { "1.0": { "needs": {
+                "IMPL_1": { "id":"IMPL_1","type":"impl","links":["SWREQ_1","SPEC_1","TEST_1"] },
+                "TEST_1": { "id":"TEST_1","type":"test","links":["IMPL_1","SPEC_1"] },
+                "SWREQ_1": { "id":"SWREQ_1","type":"swreq","links":[] },
+                "SPEC_1": { "id":"SPEC_1","type":"spec","links":[] }
+              } } }
+            }"#,
+        )
+        .unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [
+                { "rule_id": "link_targets", "selector": { "kinds": ["impl", "test"] },
+                  "params": { "allowed": { "impl": ["swreq", "spec"] },
+                              "forbidden": { "test": ["impl"] } } }
+            ] }"#,
+        )
+        .unwrap();
+
+        let mut found: Vec<(NodeId, Vec<NodeId>)> = evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .inspect(|i| assert_eq!(i.code, IssueCode::LinkTarget))
+            .map(|i| (i.subject, i.related))
+            .collect();
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec![
+                (NodeId::from("IMPL_1"), vec![NodeId::from("TEST_1")]),
+                (NodeId::from("TEST_1"), vec![NodeId::from("IMPL_1")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn source_kinds_are_resolved_through_aliases() {
+        let ds = populate_from_str(
+            r#"{
+              "current_version": "1.0",
+              "versions": { "1.0": { "needs": {
+                "IMPL_1": { "id":"IMPL_1","type":"impl","links":["SWREQ_1","TEST_1"] },
+                "TEST_1": { "id":"TEST_1","type":"test","links":[] },
+                "SWREQ_1": { "id":"SWREQ_1","type":"swreq","links":[] }
+              } } }
+            }"#,
+        )
+        .unwrap();
+        let defs = serde_json::from_str(
+            r#"{ "impl": { "aliases": ["implementation"] }, "swreq": { "aliases": ["sw_req"] } }"#,
+        )
+        .unwrap();
+        let g = Graph::with_types(
+            ds.access_current_version().unwrap(),
+            &[],
+            NodeTypes::new(&defs),
+        )
+        .unwrap();
+        let policy = load_policy_from_str(
+            r#"{ "version": 1, "rules": [
+                { "rule_id": "link_targets", "selector": { "kinds": ["impl"] },
+                  "params": { "allowed": { "implementation": ["sw_req"] } } }
+            ] }"#,
+        )
+        .unwrap();
+
+        let found: Vec<(NodeId, Vec<NodeId>)> = evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .map(|i| (i.subject, i.related))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(NodeId::from("IMPL_1"), vec![NodeId::from("TEST_1")])]
+        );
+    }
+}
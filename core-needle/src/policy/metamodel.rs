@@ -0,0 +1,192 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::policy::field_type::FieldType;
+use crate::policy::model::{Rule, Selector};
+use crate::representation::{LinkType, NodeKind, Severity};
+
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+// Declarative description of every need type, compiled into ordinary policy
+// rules so its issues go through the same evaluation, reports and gating.
+// Rule codes name the violated element, e.g. `metamodel.req.asil.type`.
+#[derive(Debug, Deserialize)]
+pub struct Metamodel {
+    pub version: u32,
+    pub severity: Option<Severity>,
+    pub types: BTreeMap<String, TypeDef>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TypeDef {
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldDef>,
+    // Only the listed link types are allowed, leave out to not check links
+    pub links: Option<BTreeMap<LinkType, LinkDef>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FieldDef {
+    #[serde(default)]
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub ty: Option<FieldType>,
+    // Accepted values of an `enum`
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LinkDef {
+    // Target need types, empty means any
+    #[serde(default)]
+    pub targets: Vec<NodeKind>,
+    #[serde(default)]
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Metamodel {
+    pub fn compile(&self) -> Vec<Rule> {
+        let mut out = Vec::new();
+
+        for (kind, def) in &self.types {
+            let rule = |rule_id: &str, element: String, params: serde_json::Value| Rule {
+                rule_id: rule_id.to_string(),
+                selector: Selector {
+                    kinds: vec![NodeKind::from_str(kind)],
+                    ..Default::default()
+                },
+                params,
+                severity: self.severity,
+                code: Some(format!("metamodel.{kind}.{element}")),
+                message: None,
+            };
+
+            for (field, fd) in &def.fields {
+                if fd.required {
+                    out.push(rule(
+                        "field_present",
+                        format!("{field}.required"),
+                        json!({ "field": field }),
+                    ));
+                }
+                if let Some(ty) = fd.ty {
+                    out.push(rule(
+                        "field_type",
+                        format!("{field}.type"),
+                        json!({ "field": field, "type": ty, "values": fd.values }),
+                    ));
+                }
+            }
+
+            let Some(links) = &def.links else {
+                continue;
+            };
+            let allowed: Vec<&LinkType> = links.keys().collect();
+            out.push(rule(
+                "allowed_link_types",
+                "links".to_string(),
+                json!({ "allowed": allowed }),
+            ));
+            for (link_type, ld) in links {
+                if !ld.targets.is_empty() {
+                    out.push(rule(
+                        "link_targets",
+                        format!("{link_type}.targets"),
+                        json!({ "allowed": { kind: ld.targets }, "link_types": [link_type] }),
+                    ));
+                }
+                if ld.min > 0 || ld.max.is_some() {
+                    out.push(rule(
+                        "has_outgoing",
+                        format!("{link_type}.cardinality"),
+                        json!({ "min": ld.min, "max": ld.max, "link_types": [link_type] }),
+                    ));
+                }
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+    use crate::policy::{Policies, evaluate_rules, validate};
+    use crate::representation::Graph;
+
+    const METAMODEL: &str = r#"{
+        "version": 1,
+        "types": {
+            "req": {
+                "fields": {
+                    "status": { "required": true, "type": "enum", "values": ["open", "approved"] }
+                },
+                "links": {
+                    "links": { "targets": ["spec"], "min": 1, "max": 1 }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn metamodel_compiles_into_named_checks() {
+        let mm: Metamodel = serde_json::from_str(METAMODEL).unwrap();
+        let policy = Policies {
+            version: 1,
+            defaults: Default::default(),
+            link_types: Vec::new(),
+            types: Default::default(),
+            rules: mm.compile(),
+        };
+        assert!(validate(&policy).is_empty());
+
+        let ds = populate_from_str(
+            r#"{
+              "current_version": "1.0",
+              "versions": { "1.0": { "needs": {
+                "REQ_1": { "id":"REQ_1","type":"req","status":"open","links":["SPEC_1"] },
+                "REQ_2": { "id":"REQ_2","type":"req","status":"done","links":["SPEC_1","TEST_1"],
+                           "verifies":["TEST_1"], "verifies_back":[] },
+                "SPEC_1": { "id":"SPEC_1","type":"spec","links":[] },
+                "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+              } } }
+            }"#,
+        )
+        .unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+
+        // Issues carry the code of the violated element too
+        let mut violated: Vec<String> = evaluate_rules(&g, &policy)
+            .unwrap()
+            .iter()
+            .flat_map(|r| {
+                assert!(
+                    r.issues
+                        .iter()
+                        .all(|i| i.code.to_str() == r.rule.code_or_id())
+                );
+                r.issues.iter().map(|i| format!("{} {}", i.code, i.subject))
+            })
+            .collect();
+        violated.sort();
+
+        assert_eq!(
+            violated,
+            vec![
+                "metamodel.req.links REQ_2",
+                "metamodel.req.links.cardinality REQ_2",
+                "metamodel.req.links.targets REQ_2",
+                "metamodel.req.status.type REQ_2",
+            ]
+        );
+    }
+}
@@ -4,13 +4,28 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+pub mod allowed_link_types;
+pub mod condition;
+pub mod coverage;
 pub mod evaluator;
 pub mod field_present;
+pub mod field_type;
+pub mod has_incoming;
 pub mod has_outgoing;
+pub mod link_targets;
+pub mod metamodel;
 pub mod model;
+pub mod no_cycles;
+pub mod path_pattern;
 pub mod reach_kind;
+pub mod reach_path;
 pub mod registry;
+pub mod selector;
+pub mod validation;
 
-pub use evaluator::evaluate;
+pub use coverage::{Metric, MetricKind};
+pub use evaluator::{RuleReport, coded_issues, evaluate, evaluate_rules, retain_issues};
+pub use metamodel::Metamodel;
 pub use model::{Defaults, Policies, Rule, Selector};
-pub use registry::{Registry, RuleFn};
+pub use registry::{IssuesFn, MeasureFn, Registry, RuleFn};
+pub use validation::{Diagnostic, DiagnosticKind, validate, validate_for};
@@ -4,17 +4,29 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+use crate::error::{Error, Result};
 use crate::representation::issue::Severity;
-use crate::representation::node_kind::NodeKind;
+use crate::representation::link_type::LinkType;
+use crate::representation::{NodeKind, TypeDef};
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+pub use crate::policy::selector::Selector;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Deserialize)]
 pub struct Policies {
     pub version: u32,
     #[serde(default)]
     pub defaults: Defaults,
+    // Link types which can't be discovered from the export
+    #[serde(default)]
+    pub link_types: Vec<LinkType>,
+    // Need types with their aliases and "is a" parent, types used by the
+    // export don't have to be declared
+    #[serde(default)]
+    pub types: BTreeMap<NodeKind, TypeDef>,
     pub rules: Vec<Rule>,
 }
 
@@ -35,7 +47,23 @@ pub struct Rule {
     pub message: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct Selector {
-    pub kinds: Vec<NodeKind>,
+impl Rule {
+    // Code the rule reports under - the policy `code`, or its `rule_id`
+    // when the policy does not name one.
+    pub fn code_or_id(&self) -> &str {
+        self.code.as_deref().unwrap_or(&self.rule_id)
+    }
+
+    // Missing params are treated as an empty object so rules with only
+    // optional params don't need `"params": {}` in the policy.
+    pub fn params<T: DeserializeOwned>(&self) -> Result<T> {
+        let raw = match &self.params {
+            serde_json::Value::Null => serde_json::Value::Object(Default::default()),
+            other => other.clone(),
+        };
+        serde_json::from_value(raw).map_err(|e| Error::InvalidRuleParams {
+            rule_id: self.rule_id.clone(),
+            reason: e.to_string(),
+        })
+    }
 }
@@ -0,0 +1,121 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::Result;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Graph, Issue, IssueCode, LinkType, NodeId, Severity};
+use serde::Deserialize;
+
+pub const PARAMS: &[ParamSpec] = &[ParamSpec::optional("link_types", ParamType::LinkTypes)];
+
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    #[serde(default)]
+    link_types: Vec<LinkType>,
+}
+
+// Circular traceability among the selected needs. Every strongly connected
+// component is one issue on its smallest member, self links count as well.
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
+
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("NO_CYCLES");
+    let msg = rule.message.as_deref().unwrap_or("circular traceability");
+
+    let mut out = Vec::new();
+    for component in g.strongly_connected(selected, &params.link_types) {
+        let first = component[0];
+        let is_cycle = component.len() > 1 || g.out_via(first, &params.link_types).contains(&first);
+        if !is_cycle {
+            continue;
+        }
+
+        let members: Vec<NodeId> = component.into_iter().cloned().collect();
+        let listed: Vec<&str> = members.iter().map(AsRef::as_ref).collect();
+        out.push(Issue {
+            severity: sev,
+            code: IssueCode::from_rule_code(code),
+            subject: first.clone(),
+            detail: format!("{msg}: {}", listed.join(", ")),
+            related: members,
+            key: None,
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::{Graph, IssueCode, NodeId};
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": { "1.0": { "needs": {
+        "REQ_A": { "id":"REQ_A","type":"req","links":["REQ_B"] },
+        "REQ_B": { "id":"REQ_B","type":"req","links":["REQ_C"],
+                   "derives":["REQ_A"], "derives_back":[] },
+        "REQ_C": { "id":"REQ_C","type":"req","links":["REQ_A"] },
+        "SPEC_1": { "id":"SPEC_1","type":"spec","links":["SPEC_1","IMPL_1"] },
+        "IMPL_1": { "id":"IMPL_1","type":"impl","links":["SPEC_1"] }
+      } } }
+    }"#;
+
+    fn cycles(policy: &str) -> Vec<(NodeId, Vec<NodeId>)> {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(policy).unwrap();
+        evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .inspect(|i| assert_eq!(i.code, IssueCode::Cycle))
+            .map(|i| (i.subject, i.related))
+            .collect()
+    }
+
+    fn ids(raw: &[&str]) -> Vec<NodeId> {
+        raw.iter().map(|s| NodeId::from(*s)).collect()
+    }
+
+    #[test]
+    fn each_cycle_is_reported_once() {
+        let found =
+            cycles(r#"{ "version": 1, "rules": [ { "rule_id": "no_cycles", "selector": {} } ] }"#);
+        assert_eq!(
+            found,
+            vec![
+                (NodeId::from("IMPL_1"), ids(&["IMPL_1", "SPEC_1"])),
+                (NodeId::from("REQ_A"), ids(&["REQ_A", "REQ_B", "REQ_C"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn kinds_and_link_types_restrict_the_graph() {
+        let found = cycles(
+            r#"{ "version": 1, "rules": [ { "rule_id": "no_cycles", "selector": { "kinds": ["spec"] } } ] }"#,
+        );
+        assert_eq!(found, vec![(NodeId::from("SPEC_1"), ids(&["SPEC_1"]))]);
+
+        let found = cycles(
+            r#"{ "version": 1, "rules": [ { "rule_id": "no_cycles", "selector": { "kinds": ["req"] },
+                 "params": { "link_types": ["derives"] } } ] }"#,
+        );
+        assert!(found.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::representation::{LinkType, NodeKind};
+
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+// Ordered trace a need has to follow, e.g `req -> swreq? -> impl+ -> test`.
+// `-[implements|refines]->` only follows the given link types, a plain `->`
+// any of them. Steps can be optional (`?`), repeated (`+`) or both (`*`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern {
+    raw: String,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub kind: NodeKind,
+    // Link types of the arrow leading to this step, empty means any
+    pub link_types: Vec<LinkType>,
+    pub optional: bool,
+    pub repeated: bool,
+}
+
+impl PathPattern {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut steps = Vec::new();
+        let mut link_types = Vec::new();
+        let segments: Vec<&str> = raw.split("->").collect();
+
+        for (idx, segment) in segments.iter().enumerate() {
+            let mut text = segment.trim();
+            let mut next_link_types = Vec::new();
+
+            // `req -[implements]` - the link types belong to the next step
+            if let Some(open) = text.rfind("-[") {
+                if idx + 1 == segments.len() {
+                    return Err(format!("`{raw}` ends with a link, a need type is missing"));
+                }
+                let inner = text[open + 2..]
+                    .strip_suffix(']')
+                    .ok_or_else(|| format!("unclosed `-[` in `{segment}`"))?;
+                next_link_types = inner
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|lt| !lt.is_empty())
+                    .map(LinkType::from)
+                    .collect();
+                if next_link_types.is_empty() {
+                    return Err(format!("no link type in `{segment}`"));
+                }
+                text = text[..open].trim();
+            }
+
+            let (name, optional, repeated) = match text.chars().last() {
+                Some('?') => (&text[..text.len() - 1], true, false),
+                Some('+') => (&text[..text.len() - 1], false, true),
+                Some('*') => (&text[..text.len() - 1], true, true),
+                _ => (text, false, false),
+            };
+            let kind = NodeKind::new(name);
+            if kind.is_empty() || kind.as_str().contains(char::is_whitespace) {
+                return Err(format!("step {} of `{raw}` is not a need type", idx + 1));
+            }
+
+            steps.push(Step {
+                kind,
+                link_types: std::mem::replace(&mut link_types, next_link_types),
+                optional,
+                repeated,
+            });
+        }
+
+        Ok(Self {
+            raw: raw.trim().to_string(),
+            steps,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn kinds(&self) -> impl Iterator<Item = &NodeKind> {
+        self.steps.iter().map(|s| &s.kind)
+    }
+
+    // Whether every step from `from` on may be left out
+    pub fn rest_optional(&self, from: usize) -> bool {
+        self.steps.iter().skip(from).all(|s| s.optional)
+    }
+}
+
+impl fmt::Display for PathPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for PathPattern {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(d)?;
+        Self::parse(&raw).map_err(|e| serde::de::Error::custom(format!("invalid path: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_quantifiers_and_link_types() {
+        let p =
+            PathPattern::parse("req -[implements | refines]-> swreq? -> impl+ -> test*").unwrap();
+        let summary: Vec<(String, Vec<String>, bool, bool)> = p
+            .steps
+            .iter()
+            .map(|s| {
+                (
+                    s.kind.to_string(),
+                    s.link_types.iter().map(|l| l.to_string()).collect(),
+                    s.optional,
+                    s.repeated,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("req".into(), vec![], false, false),
+                (
+                    "swreq".into(),
+                    vec!["implements".into(), "refines".into()],
+                    true,
+                    false
+                ),
+                ("impl".into(), vec![], false, true),
+                ("test".into(), vec![], true, true),
+            ]
+        );
+        assert!(p.rest_optional(3));
+        assert!(!p.rest_optional(1));
+    }
+
+    #[test]
+    fn malformed_patterns_are_rejected() {
+        assert!(PathPattern::parse("req -> -> test").is_err());
+        assert!(PathPattern::parse("req -[]-> test").is_err());
+        assert!(PathPattern::parse("req -[implements-> test").is_err());
+        assert!(PathPattern::parse("req -[implements]").is_err());
+        assert!(PathPattern::parse("sw req -> test").is_err());
+        assert!(serde_json::from_str::<PathPattern>(r#""req ->""#).is_err());
+    }
+}
@@ -4,26 +4,54 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+use crate::error::Result;
 use crate::policy::model::{Defaults, Rule};
-use crate::representation::{Graph, Issue, IssueCode, NodeKind, Severity};
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{
+    Direction, Graph, Issue, IssueCode, LinkType, NodeId, NodeKind, Severity,
+};
 use serde::Deserialize;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+pub const PARAMS: &[ParamSpec] = &[
+    ParamSpec::required("target_kinds", ParamType::Kinds),
+    ParamSpec::optional("min", ParamType::Usize),
+    ParamSpec::optional("min_per_kind", ParamType::KindCounts),
+    ParamSpec::optional("max_hops", ParamType::Usize),
+    ParamSpec::optional("link_types", ParamType::LinkTypes),
+    ParamSpec::optional("direction", ParamType::Direction),
+];
 
 #[derive(Deserialize)]
-struct Params {
+pub(crate) struct Params {
     target_kinds: Vec<NodeKind>,
-    #[serde(default)]
+    // Distinct targets needed, `0` makes the targets optional
+    #[serde(default = "default_min")]
     min: usize,
+    // On top of `min`, e.g at least one `test` and one `testrun`
+    #[serde(default)]
+    min_per_kind: BTreeMap<NodeKind, usize>,
     #[serde(default)]
     max_hops: Option<usize>,
+    #[serde(default)]
+    link_types: Vec<LinkType>,
+    // `backward` walks the links against their direction, e.g every test
+    // reached from a req, `both` follows either way
+    #[serde(default)]
+    direction: Direction,
+}
+
+fn default_min() -> usize {
+    1
 }
 
-pub fn run(g: &Graph, rule: &Rule, defaults: &Defaults) -> Vec<Issue> {
-    let mut params: Params = serde_json::from_value(rule.params.clone()).unwrap_or(Params {
-        target_kinds: vec![],
-        min: 1,
-        max_hops: None,
-    });
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let mut params: Params = rule.params()?;
     if params.max_hops.is_none() {
         params.max_hops = defaults.max_hops;
     }
@@ -39,42 +67,218 @@ pub fn run(g: &Graph, rule: &Rule, defaults: &Defaults) -> Vec<Issue> {
         .as_deref()
         .unwrap_or("missing required reachable target");
 
-    let targets: std::collections::HashSet<_> = params.target_kinds.into_iter().collect();
+    // The detail changes with every target reached, the kinds asked for don't
+    let key: Vec<&str> = params
+        .target_kinds
+        .iter()
+        .chain(params.min_per_kind.keys())
+        .map(|k| k.as_str())
+        .collect();
+    let key = key.join(", ");
 
     let mut out = Vec::new();
-    for kind in &rule.selector.kinds {
-        for start in g.of_kind(*kind) {
-            let mut q = VecDeque::from([(start, 0usize)]);
-            let mut seen: HashSet<&crate::representation::NodeId> = HashSet::from([start]);
-            let mut hits = 0usize;
-
-            while let Some((id, d)) = q.pop_front() {
-                if d > 0 {
-                    if let Some(n) = g.nodes.get(id) {
-                        if targets.contains(&NodeKind::from_str(n.kind.as_deref().unwrap())) {
-                            hits += 1;
-                        }
-                    }
-                }
-                if d == hops {
-                    continue;
-                }
-                for nxt in g.out(id) {
-                    if seen.insert(nxt) {
-                        q.push_back((nxt, d + 1));
-                    }
+    for start in selected.iter().copied() {
+        let mut q = VecDeque::from([(start, 0usize)]);
+        let mut seen: HashSet<&NodeId> = HashSet::from([start]);
+        // Every target counts once, however many paths lead to it
+        let mut reached: Vec<&NodeId> = Vec::new();
+
+        while let Some((id, d)) = q.pop_front() {
+            // Subtypes count as well, e.g `swreq` for a `req` target
+            if d > 0
+                && (params.target_kinds.iter().any(|k| g.is_a(id, k))
+                    || params.min_per_kind.keys().any(|k| g.is_a(id, k)))
+            {
+                reached.push(id);
+            }
+            if d == hops {
+                continue;
+            }
+            for nxt in g.via(id, &params.link_types, params.direction) {
+                if seen.insert(nxt) {
+                    q.push_back((nxt, d + 1));
                 }
             }
+        }
 
-            if hits < 1.max(params.min) {
-                out.push(Issue {
-                    severity: sev,
-                    code: IssueCode::from_rule_code(code),
-                    subject: start.clone(),
-                    detail: msg.to_string(),
-                });
+        let hits = reached
+            .iter()
+            .filter(|id| params.target_kinds.iter().any(|k| g.is_a(id, k)))
+            .count();
+        let mut missing = Vec::new();
+        if hits < params.min {
+            let kinds: Vec<&str> = params.target_kinds.iter().map(|k| k.as_str()).collect();
+            missing.push(format!("{} of {}", params.min - hits, kinds.join(", ")));
+        }
+        for (kind, min) in &params.min_per_kind {
+            let count = reached.iter().filter(|id| g.is_a(id, kind)).count();
+            if count < *min {
+                missing.push(format!("{} {kind}", min - count));
             }
         }
+        if missing.is_empty() {
+            continue;
+        }
+
+        reached.sort();
+        let shown: Vec<String> = reached
+            .iter()
+            .map(|id| match g.kind_of(id) {
+                Some(kind) => format!("{id} ({kind})"),
+                None => id.to_string(),
+            })
+            .collect();
+        let shown = if shown.is_empty() {
+            "nothing".to_string()
+        } else {
+            shown.join(", ")
+        };
+        out.push(Issue {
+            severity: sev,
+            code: IssueCode::from_rule_code(code),
+            subject: start.clone(),
+            detail: format!("{msg}: reached {shown}; missing {}", missing.join(", ")),
+            related: reached.into_iter().cloned().collect(),
+            key: Some(key.clone()),
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::Graph;
+
+    const NEEDS: &str = r#"{
+      "current_version": "1.0",
+      "versions": { "1.0": { "needs": {
+        "REQ_1": { "id":"REQ_1","type":"req","links":["TEST_1","TEST_2"] },
+        "REQ_2": { "id":"REQ_2","type":"req","links":["TEST_1","RUN_1"] },
+        "REQ_3": { "id":"REQ_3","type":"req","links":[] },
+        "TEST_1": { "id":"TEST_1","type":"test","links":["RUN_1"] },
+        "TEST_2": { "id":"TEST_2","type":"test","links":[] },
+        "RUN_1": { "id":"RUN_1","type":"testrun","links":[] }
+      } } }
+    }"#;
+
+    fn details(params: &str) -> Vec<(String, String)> {
+        details_of("req", params)
+    }
+
+    fn details_of(kind: &str, params: &str) -> Vec<(String, String)> {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(&format!(
+            r#"{{ "version": 1, "rules": [ {{ "rule_id": "reach_kind",
+                 "selector": {{ "kinds": ["{kind}"] }}, "params": {params} }} ] }}"#
+        ))
+        .unwrap();
+        let mut out: Vec<(String, String)> = evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .map(|i| (i.subject.to_string(), i.detail))
+            .collect();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn min_counts_distinct_targets() {
+        // REQ_2 reaches RUN_1 twice, it still counts once
+        assert_eq!(
+            details(r#"{ "target_kinds": ["test", "testrun"], "min": 2 }"#),
+            vec![(
+                "REQ_3".into(),
+                "missing required reachable target: reached nothing; missing 2 of test, testrun"
+                    .into()
+            )]
+        );
+        assert_eq!(
+            details(r#"{ "target_kinds": ["testrun"], "min": 2 }"#),
+            vec![
+                (
+                    "REQ_1".into(),
+                    "missing required reachable target: reached RUN_1 (testrun); missing 1 of testrun"
+                        .into()
+                ),
+                (
+                    "REQ_2".into(),
+                    "missing required reachable target: reached RUN_1 (testrun); missing 1 of testrun"
+                        .into()
+                ),
+                (
+                    "REQ_3".into(),
+                    "missing required reachable target: reached nothing; missing 2 of testrun".into()
+                ),
+            ]
+        );
+        assert!(details(r#"{ "target_kinds": ["test"], "min": 0 }"#).is_empty());
+    }
+
+    #[test]
+    fn reach_kind_walks_backward_and_both_ways() {
+        // Every test has to be reached from a req
+        assert!(
+            details_of(
+                "test",
+                r#"{ "target_kinds": ["req"], "direction": "backward", "max_hops": 1 }"#
+            )
+            .is_empty()
+        );
+
+        assert_eq!(
+            details_of(
+                "test",
+                r#"{ "target_kinds": ["testrun"], "direction": "backward" }"#
+            ),
+            vec![
+                (
+                    "TEST_1".into(),
+                    "missing required reachable target: reached nothing; missing 1 of testrun"
+                        .into()
+                ),
+                (
+                    "TEST_2".into(),
+                    "missing required reachable target: reached nothing; missing 1 of testrun"
+                        .into()
+                ),
+            ]
+        );
+        assert_eq!(
+            details_of(
+                "test",
+                r#"{ "target_kinds": ["testrun"], "direction": "both" }"#
+            ),
+            vec![(
+                "TEST_2".into(),
+                "missing required reachable target: reached nothing; missing 1 of testrun".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn minimum_per_target_kind() {
+        assert_eq!(
+            details(
+                r#"{ "target_kinds": ["test", "testrun"], "max_hops": 1,
+                     "min_per_kind": { "test": 1, "testrun": 1 } }"#
+            ),
+            vec![
+                (
+                    "REQ_1".into(),
+                    "missing required reachable target: reached TEST_1 (test), TEST_2 (test); \
+                     missing 1 testrun"
+                        .into()
+                ),
+                (
+                    "REQ_3".into(),
+                    "missing required reachable target: reached nothing; \
+                     missing 1 of test, testrun, 1 test, 1 testrun"
+                        .into()
+                ),
+            ]
+        );
     }
-    out
 }
@@ -0,0 +1,246 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::Result;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::path_pattern::PathPattern;
+use crate::policy::validation::{ParamSpec, ParamType};
+use crate::representation::{Graph, Issue, IssueCode, NodeId, Severity};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub const PARAMS: &[ParamSpec] = &[ParamSpec::required("path", ParamType::PathPattern)];
+
+// Unlike `reach_kind` the targets have to be reached in order, every step
+// through a need of the step's type, e.g `req -> swreq -> impl -> test`.
+#[derive(Deserialize)]
+pub(crate) struct Params {
+    path: PathPattern,
+}
+
+// Need together with the index of the step it matched
+type State<'g> = (&'g NodeId, usize);
+
+pub fn run(
+    g: &Graph,
+    rule: &Rule,
+    selected: &[&NodeId],
+    defaults: &Defaults,
+) -> Result<Vec<Issue>> {
+    let params: Params = rule.params()?;
+    let steps = &params.path.steps;
+
+    let sev = rule
+        .severity
+        .or(defaults.severity)
+        .unwrap_or(Severity::Error);
+    let code = rule.code.as_deref().unwrap_or("REACH_PATH");
+    let msg = rule
+        .message
+        .clone()
+        .unwrap_or_else(|| format!("no path matching `{}`", params.path));
+
+    let mut out = Vec::new();
+    for start in selected.iter().copied() {
+        // Leading optional steps may be skipped by the start need as well
+        let mut queue: VecDeque<State<'_>> = VecDeque::new();
+        for (pos, step) in steps.iter().enumerate() {
+            if g.is_a(start, &step.kind) {
+                queue.push_back((start, pos));
+            }
+            if !step.optional {
+                break;
+            }
+        }
+
+        let mut seen: HashSet<State<'_>> = queue.iter().copied().collect();
+        let mut parent: HashMap<State<'_>, State<'_>> = HashMap::new();
+        let mut depth: HashMap<State<'_>, usize> = queue.iter().map(|s| (*s, 0)).collect();
+        let mut best: Option<State<'_>> = None;
+        let mut matched = false;
+
+        while let Some(state @ (id, pos)) = queue.pop_front() {
+            if params.path.rest_optional(pos + 1) {
+                matched = true;
+                break;
+            }
+            // Furthest step first, the longer path on a tie
+            if best.is_none_or(|b| (pos, depth[&state]) > (b.1, depth[&b])) {
+                best = Some(state);
+            }
+
+            let mut next = Vec::new();
+            if steps[pos].repeated {
+                next.push(pos);
+            }
+            for (j, step) in steps.iter().enumerate().skip(pos + 1) {
+                next.push(j);
+                if !step.optional {
+                    break;
+                }
+            }
+
+            for j in next {
+                for target in g.out_via(id, &steps[j].link_types) {
+                    let reached = (target, j);
+                    if g.is_a(target, &steps[j].kind) && seen.insert(reached) {
+                        parent.insert(reached, state);
+                        depth.insert(reached, depth[&state] + 1);
+                        queue.push_back(reached);
+                    }
+                }
+            }
+        }
+        if matched {
+            continue;
+        }
+
+        // Longest partial path, from the start need on
+        let mut path: Vec<&NodeId> = Vec::new();
+        let mut cur = best;
+        while let Some(state) = cur {
+            path.push(state.0);
+            cur = parent.get(&state).copied();
+        }
+        path.reverse();
+
+        let detail = match best {
+            None => format!(
+                "{msg}: {start} is not a `{}` need",
+                steps.first().map(|s| s.kind.as_str()).unwrap_or_default()
+            ),
+            Some((_, pos)) => {
+                let shown: Vec<String> = path
+                    .iter()
+                    .map(|id| match g.kind_of(id) {
+                        Some(kind) => format!("{id} ({kind})"),
+                        None => id.to_string(),
+                    })
+                    .collect();
+                let expected = steps
+                    .iter()
+                    .skip(pos + 1)
+                    .find(|s| !s.optional)
+                    .map(|s| s.kind.as_str())
+                    .unwrap_or_default();
+                format!(
+                    "{msg}: longest partial path {}, expected `{expected}` next",
+                    shown.join(" -> ")
+                )
+            }
+        };
+
+        out.push(Issue {
+            severity: sev,
+            code: IssueCode::from_rule_code(code),
+            subject: start.clone(),
+            detail,
+            related: path.into_iter().skip(1).cloned().collect(),
+            // The longest partial path changes as links are added
+            key: Some(params.path.as_str().to_string()),
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{load_policy_from_str, populate_from_str};
+    use crate::policy::evaluate;
+    use crate::representation::Graph;
+
+    const NEEDS: &str = r#"{
+      "current_version": "1.0",
+      "versions": { "1.0": { "needs": {
+        "REQ_1": { "id":"REQ_1","type":"req","links":["SWREQ_1"] },
+        "SWREQ_1": { "id":"SWREQ_1","type":"swreq","links":["IMPL_1"] },
+        "IMPL_1": { "id":"IMPL_1","type":"impl","links":["IMPL_2"] },
+        "IMPL_2": { "id":"IMPL_2","type":"impl","links":["TEST_1"] },
+        "TEST_1": { "id":"TEST_1","type":"test","links":[] },
+        "REQ_2": { "id":"REQ_2","type":"req","links":["SWREQ_2","TEST_2"] },
+        "SWREQ_2": { "id":"SWREQ_2","type":"swreq","links":[] },
+        "TEST_2": { "id":"TEST_2","type":"test","links":[] },
+        "REQ_3": { "id":"REQ_3","type":"req","links":["IMPL_3"] },
+        "IMPL_3": { "id":"IMPL_3","type":"impl","links":["TEST_1"] }
+      } } }
+    }"#;
+
+    fn details(path: &str) -> Vec<(String, String)> {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(&format!(
+            r#"{{ "version": 1, "rules": [ {{ "rule_id": "reach_path",
+                 "selector": {{ "kinds": ["req"] }}, "params": {{ "path": "{path}" }} }} ] }}"#
+        ))
+        .unwrap();
+        let mut out: Vec<(String, String)> = evaluate(&g, &policy)
+            .unwrap()
+            .into_iter()
+            .map(|i| (i.subject.to_string(), i.detail))
+            .collect();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn targets_must_be_reached_in_order() {
+        // REQ_2 reaches a test directly, which `reach_kind` would accept. REQ_1
+        // goes through two impls, one step more than the pattern allows.
+        assert_eq!(
+            details("req -> swreq -> impl -> test"),
+            vec![
+                (
+                    "REQ_1".into(),
+                    "no path matching `req -> swreq -> impl -> test`: longest partial path \
+                     REQ_1 (req) -> SWREQ_1 (swreq) -> IMPL_1 (impl), expected `test` next"
+                        .into()
+                ),
+                (
+                    "REQ_2".into(),
+                    "no path matching `req -> swreq -> impl -> test`: longest partial path \
+                     REQ_2 (req) -> SWREQ_2 (swreq), expected `impl` next"
+                        .into()
+                ),
+                (
+                    "REQ_3".into(),
+                    "no path matching `req -> swreq -> impl -> test`: longest partial path \
+                     REQ_3 (req), expected `swreq` next"
+                        .into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn optional_and_repeated_steps() {
+        assert_eq!(
+            details("req -> swreq? -> impl+ -> test"),
+            vec![(
+                "REQ_2".into(),
+                "no path matching `req -> swreq? -> impl+ -> test`: longest partial path \
+                 REQ_2 (req) -> SWREQ_2 (swreq), expected `impl` next"
+                    .into()
+            )]
+        );
+        assert_eq!(
+            details("req -[links]-> swreq* -> test"),
+            vec![
+                (
+                    "REQ_1".into(),
+                    "no path matching `req -[links]-> swreq* -> test`: longest partial path \
+                     REQ_1 (req) -> SWREQ_1 (swreq), expected `test` next"
+                        .into()
+                ),
+                (
+                    "REQ_3".into(),
+                    "no path matching `req -[links]-> swreq* -> test`: longest partial path \
+                     REQ_3 (req), expected `test` next"
+                        .into()
+                ),
+            ]
+        );
+    }
+}
@@ -4,15 +4,31 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
-use crate::policy::model::{Defaults, Policies, Rule, Selector};
+use crate::error::Result;
+use crate::policy::coverage::Metric;
+use crate::policy::model::{Defaults, Rule};
+use crate::policy::validation::ParamSpec;
 use crate::representation::graph::Graph;
-use crate::representation::{Issue, IssueCode, Severity};
-use crate::representation::{NodeId, NodeKind};
+use crate::representation::{Issue, NodeId};
 use std::collections::HashMap;
-pub type RuleFn = fn(&Graph, &Rule, &Defaults) -> Vec<Issue>;
+
+// `selected` are the needs picked by the rule's selector
+pub type RuleFn = fn(&Graph, &Rule, &[&NodeId], &Defaults) -> Result<Vec<Issue>>;
+
+// Aggregate rules also measure a value over all selected needs
+pub type MeasureFn = fn(&Graph, &Rule, &[&NodeId], &Defaults) -> Result<Metric>;
+
+// Issues of an aggregate rule derived from its measured value
+pub type IssuesFn = fn(&Metric, &Rule, &Defaults) -> Vec<Issue>;
+
+struct Entry {
+    run: RuleFn,
+    aggregate: Option<(MeasureFn, IssuesFn)>,
+    params: &'static [ParamSpec],
+}
 
 pub struct Registry {
-    rules: HashMap<&'static str, RuleFn>,
+    rules: HashMap<&'static str, Entry>,
 }
 
 impl Registry {
@@ -20,14 +36,102 @@ impl Registry {
         let mut r = Self {
             rules: HashMap::new(),
         };
-        r.rules
-            .insert("has_outgoing", crate::policy::has_outgoing::run);
-        r.rules.insert("reach_kind", crate::policy::reach_kind::run);
-        r.rules
-            .insert("field_present", crate::policy::field_present::run);
+        r.register(
+            "has_outgoing",
+            crate::policy::has_outgoing::run,
+            crate::policy::has_outgoing::PARAMS,
+        );
+        r.register(
+            "has_incoming",
+            crate::policy::has_incoming::run,
+            crate::policy::has_incoming::PARAMS,
+        );
+        r.register(
+            "reach_kind",
+            crate::policy::reach_kind::run,
+            crate::policy::reach_kind::PARAMS,
+        );
+        r.register(
+            "reach_path",
+            crate::policy::reach_path::run,
+            crate::policy::reach_path::PARAMS,
+        );
+        r.register(
+            "field_present",
+            crate::policy::field_present::run,
+            crate::policy::field_present::PARAMS,
+        );
+        r.register(
+            "field_type",
+            crate::policy::field_type::run,
+            crate::policy::field_type::PARAMS,
+        );
+        r.register(
+            "allowed_link_types",
+            crate::policy::allowed_link_types::run,
+            crate::policy::allowed_link_types::PARAMS,
+        );
+        r.register(
+            "no_cycles",
+            crate::policy::no_cycles::run,
+            crate::policy::no_cycles::PARAMS,
+        );
+        r.register(
+            "link_targets",
+            crate::policy::link_targets::run,
+            crate::policy::link_targets::PARAMS,
+        );
+        r.register(
+            "condition",
+            crate::policy::condition::run,
+            crate::policy::condition::PARAMS,
+        );
+        r.register_aggregate(
+            "coverage",
+            crate::policy::coverage::run,
+            crate::policy::coverage::measure,
+            crate::policy::coverage::issues,
+            crate::policy::coverage::PARAMS,
+        );
         r
     }
+    pub fn register(&mut self, id: &'static str, run: RuleFn, params: &'static [ParamSpec]) {
+        self.rules.insert(
+            id,
+            Entry {
+                run,
+                aggregate: None,
+                params,
+            },
+        );
+    }
+    pub fn register_aggregate(
+        &mut self,
+        id: &'static str,
+        run: RuleFn,
+        measure: MeasureFn,
+        issues: IssuesFn,
+        params: &'static [ParamSpec],
+    ) {
+        self.rules.insert(
+            id,
+            Entry {
+                run,
+                aggregate: Some((measure, issues)),
+                params,
+            },
+        );
+    }
     pub fn get(&self, id: &str) -> Option<&RuleFn> {
-        self.rules.get(id)
+        self.rules.get(id).map(|e| &e.run)
+    }
+    pub fn aggregate(&self, id: &str) -> Option<(MeasureFn, IssuesFn)> {
+        self.rules.get(id).and_then(|e| e.aggregate)
+    }
+    pub fn params(&self, id: &str) -> Option<&'static [ParamSpec]> {
+        self.rules.get(id).map(|e| e.params)
+    }
+    pub fn ids(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.rules.keys().copied()
     }
 }
@@ -0,0 +1,275 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::filter::Filter;
+use crate::pattern::{Pattern, glob_match};
+use crate::representation::{Graph, Node, NodeId, NodeKind, NodeTypes};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Needs a rule applies to. Every given predicate has to hold, the ones left
+// out don't filter anything - without `kinds` all needs are candidates.
+// `kinds` also selects subtypes, e.g `req` covers `swreq` when it "is a" `req`.
+// Unknown keys are rejected, a misspelled predicate would select every need.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Selector {
+    #[serde(default)]
+    pub kinds: Vec<NodeKind>,
+    // One status or a list of accepted ones
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub status: Vec<String>,
+    #[serde(default)]
+    pub tags_any: Vec<String>,
+    #[serde(default)]
+    pub tags_all: Vec<String>,
+    #[serde(default)]
+    pub id_regex: Option<Pattern>,
+    // Matched against the docname (`adas/*`) or the document path (`adas/*.rst`)
+    #[serde(default)]
+    pub docname_glob: Option<String>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldMatch>,
+    // Sphinx-Needs filter string, e.g. `status != 'rejected' and 'safety' in tags`
+    #[serde(default)]
+    pub filter: Option<Filter>,
+}
+
+// `"asil": "B"` compares values, `"owner": { "regex": "^team-" }` matches the
+// string form. For list options it's enough when one item matches.
+#[derive(Debug)]
+pub enum FieldMatch {
+    Equals(Value),
+    Regex(Pattern),
+}
+
+impl Selector {
+    // Nodes the rule applies to, rules get them from the evaluator so the
+    // selector is evaluated once per rule.
+    pub fn select<'g>(&self, g: &'g Graph) -> Vec<&'g NodeId> {
+        let mut candidates: Vec<&NodeId> = if self.kinds.is_empty() {
+            g.nodes.keys().collect()
+        } else {
+            self.kinds.iter().flat_map(|k| g.of_type(k)).collect()
+        };
+        // Kinds may overlap through the hierarchy
+        candidates.sort();
+        candidates.dedup();
+
+        candidates
+            .into_iter()
+            .filter(|id| {
+                g.nodes
+                    .get(*id)
+                    .is_some_and(|n| self.matches(&g.types, id, n))
+            })
+            .collect()
+    }
+
+    pub fn matches(&self, types: &NodeTypes, id: &NodeId, node: &Node) -> bool {
+        let kind = types.resolve(node.kind.as_deref().unwrap_or_default());
+        let tags = node.tags.as_deref().unwrap_or_default();
+
+        (self.kinds.is_empty() || self.kinds.iter().any(|k| types.is_a(&kind, k)))
+            && (self.status.is_empty()
+                || node
+                    .status
+                    .as_ref()
+                    .is_some_and(|s| self.status.contains(s)))
+            && (self.tags_any.is_empty() || self.tags_any.iter().any(|t| tags.contains(t)))
+            && self.tags_all.iter().all(|t| tags.contains(t))
+            && self
+                .id_regex
+                .as_ref()
+                .is_none_or(|re| re.is_match(id.as_ref()))
+            && self.docname_glob.as_deref().is_none_or(|glob| {
+                node.docname().is_some_and(|d| glob_match(glob, d))
+                    || node.doc_path().is_some_and(|p| glob_match(glob, &p))
+            })
+            && self
+                .fields
+                .iter()
+                .all(|(key, m)| m.matches(node.field_value(key).as_ref()))
+            && self.filter.as_ref().is_none_or(|f| f.matches(node))
+    }
+}
+
+impl FieldMatch {
+    pub fn matches(&self, value: Option<&Value>) -> bool {
+        match (self, value) {
+            (_, None | Some(Value::Null)) => false,
+            (Self::Equals(expected), Some(Value::Array(items))) if !expected.is_array() => {
+                items.contains(expected)
+            }
+            (Self::Equals(expected), Some(v)) => v == expected,
+            (Self::Regex(re), Some(Value::Array(items))) => {
+                items.iter().any(|v| re.is_match(&scalar(v)))
+            }
+            (Self::Regex(re), Some(v)) => re.is_match(&scalar(v)),
+        }
+    }
+}
+
+// Strings without the JSON quotes, anything else as JSON
+fn scalar(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldMatch {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let raw = Value::deserialize(d)?;
+        match raw {
+            Value::Object(map) if map.len() == 1 && map.contains_key("regex") => {
+                let re = map["regex"]
+                    .as_str()
+                    .ok_or_else(|| serde::de::Error::custom("`regex` must be a string"))?;
+                Pattern::new(re)
+                    .map(Self::Regex)
+                    .map_err(serde::de::Error::custom)
+            }
+            other => Ok(Self::Equals(other)),
+        }
+    }
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(d)? {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "REQ_1": { "id":"REQ_1","type":"req","status":"approved","tags":["safety","adas"],
+                       "docname":"adas/requirements","doctype":".rst","asil":"B" },
+            "REQ_2": { "id":"REQ_2","type":"req","status":"draft","tags":["safety"],
+                       "docname":"adas/requirements","doctype":".rst","asil":"QM" },
+            "REQ_X3": { "id":"REQ_X3","type":"req","status":"approved","tags":["comfort"],
+                        "docname":"body/requirements","doctype":".rst","owners":["team-body"] },
+            "IMPL_1": { "id":"IMPL_1","type":"impl","status":"approved","tags":["safety"] }
+          }
+        }
+      }
+    }"#;
+
+    fn selected(json: &str) -> Vec<String> {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let sel: Selector = serde_json::from_str(json).unwrap();
+        let mut ids: Vec<String> = sel.select(&g).into_iter().map(|i| i.to_string()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn predicates_are_combined() {
+        assert_eq!(
+            selected(r#"{ "kinds": ["req"], "status": "approved", "tags_any": ["safety"] }"#),
+            vec!["REQ_1"]
+        );
+        assert_eq!(
+            selected(r#"{ "status": ["approved"], "tags_all": ["safety"] }"#),
+            vec!["IMPL_1", "REQ_1"]
+        );
+        assert_eq!(
+            selected(r#"{ "id_regex": "^REQ_[0-9]+$" }"#),
+            vec!["REQ_1", "REQ_2"]
+        );
+        assert_eq!(
+            selected(r#"{ "docname_glob": "adas/*" }"#),
+            vec!["REQ_1", "REQ_2"]
+        );
+        assert_eq!(
+            selected(r#"{ "docname_glob": "body/*.rst" }"#),
+            vec!["REQ_X3"]
+        );
+        let err = serde_json::from_str::<Selector>(r#"{ "kinsd": ["req"] }"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `kinsd`"));
+    }
+
+    #[test]
+    fn field_predicates() {
+        assert_eq!(selected(r#"{ "fields": { "asil": "B" } }"#), vec!["REQ_1"]);
+        assert_eq!(
+            selected(r#"{ "fields": { "asil": { "regex": "^(B|QM)$" } } }"#),
+            vec!["REQ_1", "REQ_2"]
+        );
+        assert_eq!(
+            selected(r#"{ "fields": { "owners": { "regex": "^team-" } } }"#),
+            vec!["REQ_X3"]
+        );
+        assert_eq!(
+            selected(r#"{ "kinds": ["req"], "filter": "'safety' in tags and asil != 'QM'" }"#),
+            vec!["REQ_1"]
+        );
+        assert_eq!(
+            selected(r#"{ "fields": { "tags": "adas" } }"#),
+            vec!["REQ_1"]
+        );
+        assert!(
+            serde_json::from_str::<Selector>(r#"{ "fields": { "a": { "regex": "(" } } }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn kinds_select_subtypes_and_aliases() {
+        let ds = populate_from_str(
+            r#"{ "current_version": "1.0", "versions": { "1.0": { "needs": {
+                "REQ_1": { "id":"REQ_1","type":"req" },
+                "SWREQ_1": { "id":"SWREQ_1","type":"swreq" },
+                "SWREQ_2": { "id":"SWREQ_2","type":"sw_req" },
+                "IMPL_1": { "id":"IMPL_1","type":"impl" }
+            } } } }"#,
+        )
+        .unwrap();
+        let defs = serde_json::from_str(r#"{ "swreq": { "is_a": "req", "aliases": ["sw_req"] } }"#)
+            .unwrap();
+        let g = Graph::with_types(
+            ds.access_current_version().unwrap(),
+            &[],
+            NodeTypes::new(&defs),
+        )
+        .unwrap();
+
+        let ids = |json: &str| -> Vec<String> {
+            let sel: Selector = serde_json::from_str(json).unwrap();
+            sel.select(&g).into_iter().map(|i| i.to_string()).collect()
+        };
+        assert_eq!(
+            ids(r#"{ "kinds": ["req"] }"#),
+            vec!["REQ_1", "SWREQ_1", "SWREQ_2"]
+        );
+        assert_eq!(
+            ids(r#"{ "kinds": ["sw_req"] }"#),
+            vec!["SWREQ_1", "SWREQ_2"]
+        );
+        assert_eq!(
+            ids(r#"{ "kinds": ["req", "swreq"] }"#),
+            vec!["REQ_1", "SWREQ_1", "SWREQ_2"]
+        );
+    }
+}
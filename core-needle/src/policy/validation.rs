@@ -0,0 +1,655 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::filter::Filter;
+use crate::policy::coverage::MetricKind;
+use crate::policy::field_type::FieldType;
+use crate::policy::model::Policies;
+use crate::policy::path_pattern::PathPattern;
+use crate::policy::registry::Registry;
+use crate::representation::{Direction, Graph, NodeKind, NodeTypes};
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+// Static description of the params a built-in rule accepts. It's only used to
+// check the policy up front - rules still deserialize their own `Params`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamType {
+    Usize,
+    String,
+    Kinds,
+    LinkTypes,
+    Filter,
+    // Need type to a list of need types
+    KindMap,
+    Strings,
+    FieldType,
+    // Ordered need types, e.g `req -> swreq -> test`
+    PathPattern,
+    Direction,
+    // Need type to a count
+    KindCounts,
+    Number,
+    Metric,
+    // Inner rule, `{ "rule_id": ..., "params": ... }`
+    Rule,
+}
+
+impl ParamType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Usize => "non-negative integer",
+            Self::String => "string",
+            Self::Kinds => "list of need types",
+            Self::LinkTypes => "list of link types",
+            Self::Filter => "filter string",
+            Self::KindMap => "map of need type to a list of need types",
+            Self::Strings => "list of strings",
+            Self::FieldType => "field type",
+            Self::PathPattern => "path pattern",
+            Self::Direction => "direction (forward, backward or both)",
+            Self::KindCounts => "map of need type to a non-negative integer",
+            Self::Number => "number",
+            Self::Metric => "metric (count, ratio or fan_out)",
+            Self::Rule => "rule object with a `rule_id`",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub ty: ParamType,
+    pub required: bool,
+}
+
+impl ParamSpec {
+    pub const fn required(name: &'static str, ty: ParamType) -> Self {
+        Self {
+            name,
+            ty,
+            required: true,
+        }
+    }
+
+    pub const fn optional(name: &'static str, ty: ParamType) -> Self {
+        Self {
+            name,
+            ty,
+            required: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    UnknownRule,
+    UnknownParam,
+    MissingParam,
+    WrongType,
+    UnknownKind,
+    InvalidValue,
+}
+
+impl DiagnosticKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::UnknownRule => "unknown_rule",
+            Self::UnknownParam => "unknown_param",
+            Self::MissingParam => "missing_param",
+            Self::WrongType => "wrong_type",
+            Self::UnknownKind => "unknown_kind",
+            Self::InvalidValue => "invalid_value",
+        }
+    }
+
+    // Problems the rule can't run with, as opposed to e.g an unknown need type
+    pub fn skips_rule(&self) -> bool {
+        matches!(
+            self,
+            Self::UnknownRule | Self::MissingParam | Self::WrongType | Self::InvalidValue
+        )
+    }
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
+// Problem found in the policy itself, `rule` is the index in `Policies::rules`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub rule: usize,
+    pub rule_id: String,
+    pub kind: DiagnosticKind,
+    pub detail: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rule #{} `{}`: {}", self.rule, self.rule_id, self.detail)
+    }
+}
+
+// Need types are open, without the export only the ones declared in `types`
+// are known - when there are none the types aren't checked at all.
+pub fn validate(p: &Policies) -> Vec<Diagnostic> {
+    validate_with(p, &Registry::builtins(), &NodeTypes::new(&p.types))
+}
+
+// Same as `validate`, need types are checked against the ones used by the
+// export too.
+pub fn validate_for(p: &Policies, g: &Graph) -> Vec<Diagnostic> {
+    validate_with(p, &Registry::builtins(), &g.types)
+}
+
+pub fn validate_with(p: &Policies, reg: &Registry, types: &NodeTypes) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+    let unknown = |kind: &NodeKind| !types.is_empty() && !types.is_known(kind);
+
+    for (idx, rule) in p.rules.iter().enumerate() {
+        let mut push = |kind: DiagnosticKind, detail: String| {
+            out.push(Diagnostic {
+                rule: idx,
+                rule_id: rule.rule_id.clone(),
+                kind,
+                detail,
+            })
+        };
+
+        for kind in rule.selector.kinds.iter().filter(|k| unknown(k)) {
+            push(
+                DiagnosticKind::UnknownKind,
+                format!("selector contains unknown need type `{kind}`"),
+            );
+        }
+
+        let Some(specs) = reg.params(&rule.rule_id) else {
+            push(
+                DiagnosticKind::UnknownRule,
+                format!("rule `{}` is not registered", rule.rule_id),
+            );
+            continue;
+        };
+
+        let empty = serde_json::Map::new();
+        let params = match &rule.params {
+            Value::Null => &empty,
+            Value::Object(map) => map,
+            _ => {
+                push(
+                    DiagnosticKind::WrongType,
+                    "params must be an object".to_string(),
+                );
+                continue;
+            }
+        };
+
+        check_params(specs, params, reg, &unknown, &mut push);
+
+        // The share of passing needs is only defined against an inner rule.
+        if params.get("metric").and_then(Value::as_str) == Some("ratio")
+            && let Some(spec) = specs.iter().find(|s| s.ty == ParamType::Rule)
+            && params.get(spec.name).is_none_or(Value::is_null)
+        {
+            push(
+                DiagnosticKind::MissingParam,
+                format!("param `{}` is required for the `ratio` metric", spec.name),
+            );
+        }
+    }
+
+    out
+}
+
+fn check_params(
+    specs: &[ParamSpec],
+    params: &serde_json::Map<String, Value>,
+    reg: &Registry,
+    unknown: &impl Fn(&NodeKind) -> bool,
+    push: &mut dyn FnMut(DiagnosticKind, String),
+) {
+    for key in params.keys() {
+        if !specs.iter().any(|s| s.name == key) {
+            push(
+                DiagnosticKind::UnknownParam,
+                format!("unknown param `{key}`"),
+            );
+        }
+    }
+
+    for spec in specs {
+        match params.get(spec.name) {
+            None | Some(Value::Null) if spec.required => push(
+                DiagnosticKind::MissingParam,
+                format!("missing required param `{}`", spec.name),
+            ),
+            None | Some(Value::Null) => {}
+            Some(value) => {
+                check_param(spec, value, unknown, push);
+                if spec.ty == ParamType::Rule
+                    && let Some(inner) = value.get("rule_id").and_then(Value::as_str)
+                {
+                    check_inner(spec, inner, value.get("params"), reg, unknown, push);
+                }
+            }
+        }
+    }
+}
+
+// A rule nested in a param is checked like a top level one, its
+// diagnostics are reported on the outer rule.
+fn check_inner(
+    spec: &ParamSpec,
+    inner: &str,
+    params: Option<&Value>,
+    reg: &Registry,
+    unknown: &impl Fn(&NodeKind) -> bool,
+    push: &mut dyn FnMut(DiagnosticKind, String),
+) {
+    let Some(specs) = reg.params(inner) else {
+        push(
+            DiagnosticKind::UnknownRule,
+            format!("param `{}` uses unregistered rule `{inner}`", spec.name),
+        );
+        return;
+    };
+
+    let mut push = |kind: DiagnosticKind, detail: String| {
+        push(
+            kind,
+            format!("param `{}` rule `{inner}`: {detail}", spec.name),
+        )
+    };
+    let empty = serde_json::Map::new();
+    match params {
+        None | Some(Value::Null) => check_params(specs, &empty, reg, unknown, &mut push),
+        Some(Value::Object(map)) => check_params(specs, map, reg, unknown, &mut push),
+        Some(_) => push(
+            DiagnosticKind::WrongType,
+            "params must be an object".to_string(),
+        ),
+    }
+}
+
+fn check_param(
+    spec: &ParamSpec,
+    value: &Value,
+    unknown: &impl Fn(&NodeKind) -> bool,
+    push: &mut dyn FnMut(DiagnosticKind, String),
+) {
+    let type_ok = match spec.ty {
+        ParamType::Usize => value.is_u64(),
+        ParamType::Number => value.is_number(),
+        ParamType::Metric => value.as_str().is_some_and(|m| MetricKind::ALL.contains(&m)),
+        ParamType::Rule => value.get("rule_id").is_some_and(Value::is_string),
+        ParamType::String | ParamType::Filter | ParamType::PathPattern => value.is_string(),
+        ParamType::FieldType => value.as_str().is_some_and(|t| FieldType::ALL.contains(&t)),
+        ParamType::Direction => value.as_str().is_some_and(|d| Direction::ALL.contains(&d)),
+        ParamType::Kinds | ParamType::LinkTypes | ParamType::Strings => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(Value::is_string)),
+        ParamType::KindCounts => value
+            .as_object()
+            .is_some_and(|map| map.values().all(Value::is_u64)),
+        ParamType::KindMap => value.as_object().is_some_and(|map| {
+            map.values().all(|v| {
+                v.as_array()
+                    .is_some_and(|items| items.iter().all(Value::is_string))
+            })
+        }),
+    };
+
+    if !type_ok {
+        push(
+            DiagnosticKind::WrongType,
+            format!("param `{}` must be a {}", spec.name, spec.ty.as_str()),
+        );
+        return;
+    }
+
+    if spec.ty == ParamType::Filter
+        && let Some(Err(e)) = value.as_str().map(Filter::parse)
+    {
+        push(
+            DiagnosticKind::InvalidValue,
+            format!("param `{}` is not a valid filter: {e}", spec.name),
+        );
+    }
+
+    let path = match spec.ty {
+        ParamType::PathPattern => value.as_str().map(PathPattern::parse),
+        _ => None,
+    };
+    if let Some(Err(e)) = &path {
+        push(
+            DiagnosticKind::InvalidValue,
+            format!("param `{}` is not a valid path: {e}", spec.name),
+        );
+    }
+
+    let kinds: Vec<&str> = match spec.ty {
+        ParamType::Kinds => value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect(),
+        ParamType::KindMap => value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .flat_map(|(k, v)| {
+                std::iter::once(k.as_str())
+                    .chain(v.as_array().into_iter().flatten().filter_map(Value::as_str))
+            })
+            .collect(),
+        ParamType::KindCounts => value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(k, _)| k.as_str())
+            .collect(),
+        ParamType::PathPattern => match &path {
+            Some(Ok(p)) => p.kinds().map(|k| k.as_str()).collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    for kind in kinds {
+        if unknown(&NodeKind::from_str(kind)) {
+            push(
+                DiagnosticKind::UnknownKind,
+                format!("param `{}` contains unknown need type `{kind}`", spec.name),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::load_policy_from_str;
+    use crate::policy::{
+        allowed_link_types, condition, coverage, field_present, field_type, has_outgoing,
+        link_targets, no_cycles, reach_kind, reach_path,
+    };
+    use serde::de::{self, DeserializeOwned, Visitor};
+    use serde_json::json;
+
+    fn kinds_of(json: &str) -> Vec<DiagnosticKind> {
+        let p = load_policy_from_str(json).expect("parse policy");
+        validate(&p).into_iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn valid_policy_has_no_diagnostics() {
+        let json = r#"{
+            "version": 1,
+            "rules": [
+                { "rule_id": "has_outgoing", "selector": { "kinds": ["impl"] } },
+                { "rule_id": "reach_kind", "selector": { "kinds": ["req"] },
+                  "params": { "target_kinds": ["test"], "min": 1, "max_hops": null } },
+                { "rule_id": "field_present", "selector": { "kinds": ["impl"] },
+                  "params": { "field": "url" } },
+                { "rule_id": "link_targets", "selector": { "kinds": ["impl"] },
+                  "params": { "allowed": { "impl": ["swreq", "spec"] } } }
+            ]
+        }"#;
+        assert!(kinds_of(json).is_empty());
+    }
+
+    #[test]
+    fn unknown_rule_is_reported() {
+        let json = r#"{
+            "version": 1,
+            "rules": [
+                { "rule_id": "external_merge", "selector": { "kinds": ["impl"] },
+                  "params": { "dataset": "merges.json" } }
+            ]
+        }"#;
+        assert_eq!(kinds_of(json), vec![DiagnosticKind::UnknownRule]);
+    }
+
+    #[test]
+    fn bad_params_are_reported() {
+        let json = r#"{
+            "version": 1,
+            "types": { "req": {}, "impl": {}, "test": {} },
+            "rules": [
+                { "rule_id": "reach_kind", "selector": { "kinds": ["req", "rqe"] },
+                  "params": { "target_kinds": ["tset"], "min": "1", "hops": 2 } },
+                { "rule_id": "field_present", "selector": { "kinds": ["impl"] } },
+                { "rule_id": "condition", "selector": { "kinds": ["req"] },
+                  "params": { "filter": "status ==" } },
+                { "rule_id": "reach_path", "selector": { "kinds": ["req"] },
+                  "params": { "path": "req -> tset" } },
+                { "rule_id": "reach_path", "selector": { "kinds": ["req"] },
+                  "params": { "path": "req -[]-> test" } },
+                { "rule_id": "coverage", "selector": { "kinds": ["req"] },
+                  "params": { "metric": "share", "min": 0.9,
+                              "rule": { "rule_id": "reach_test" } } }
+            ]
+        }"#;
+        assert_eq!(
+            kinds_of(json),
+            vec![
+                DiagnosticKind::UnknownKind,
+                DiagnosticKind::UnknownParam,
+                DiagnosticKind::UnknownKind,
+                DiagnosticKind::WrongType,
+                DiagnosticKind::MissingParam,
+                DiagnosticKind::InvalidValue,
+                DiagnosticKind::UnknownKind,
+                DiagnosticKind::InvalidValue,
+                DiagnosticKind::WrongType,
+                DiagnosticKind::UnknownRule,
+            ]
+        );
+    }
+
+    #[test]
+    fn coverage_ratio_needs_a_valid_inner_rule() {
+        let json = r#"{
+            "version": 1,
+            "rules": [
+                { "rule_id": "coverage", "selector": {}, "params": { "metric": "ratio", "min": 0.9 } },
+                { "rule_id": "coverage", "selector": {},
+                  "params": { "metric": "ratio", "min": 0.9,
+                              "rule": { "rule_id": "reach_kind", "params": {} } } },
+                { "rule_id": "coverage", "selector": {},
+                  "params": { "metric": "ratio", "min": 0.9,
+                              "rule": { "rule_id": "reach_kind",
+                                        "params": { "target_kinds": "test", "hops": 1 } } } }
+            ]
+        }"#;
+        let p = load_policy_from_str(json).unwrap();
+        let found: Vec<_> = validate(&p)
+            .into_iter()
+            .map(|d| (d.rule, d.kind, d.detail))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    0,
+                    DiagnosticKind::MissingParam,
+                    "param `rule` is required for the `ratio` metric".to_string()
+                ),
+                (
+                    1,
+                    DiagnosticKind::MissingParam,
+                    "param `rule` rule `reach_kind`: missing required param `target_kinds`"
+                        .to_string()
+                ),
+                (
+                    2,
+                    DiagnosticKind::UnknownParam,
+                    "param `rule` rule `reach_kind`: unknown param `hops`".to_string()
+                ),
+                (
+                    2,
+                    DiagnosticKind::WrongType,
+                    "param `rule` rule `reach_kind`: param `target_kinds` must be a list of need types"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn need_types_come_from_config_and_export() {
+        let json = r#"{
+            "version": 1,
+            "types": { "swreq": { "is_a": "req", "aliases": ["sw_req"] } },
+            "rules": [
+                { "rule_id": "reach_kind", "selector": { "kinds": ["sw_req", "hazard"] },
+                  "params": { "target_kinds": ["req", "test"] } }
+            ]
+        }"#;
+        let p = load_policy_from_str(json).unwrap();
+        let details: Vec<String> = validate(&p).into_iter().map(|d| d.detail).collect();
+        assert_eq!(
+            details,
+            vec![
+                "selector contains unknown need type `hazard`",
+                "param `target_kinds` contains unknown need type `test`",
+            ]
+        );
+
+        let ds = crate::io::populate_from_str(
+            r#"{ "current_version": "1.0", "versions": { "1.0": { "needs": {
+                "H_1": { "id":"H_1","type":"hazard","links":[] },
+                "T_1": { "id":"T_1","type":"test","links":[] }
+            } } } }"#,
+        )
+        .unwrap();
+        let g = Graph::with_types(
+            ds.access_current_version().unwrap(),
+            &[],
+            NodeTypes::new(&p.types),
+        )
+        .unwrap();
+        assert!(validate_for(&p, &g).is_empty());
+
+        // Nothing declared and no export - types can't be checked
+        let p = load_policy_from_str(
+            r#"{ "version": 1, "rules": [ { "rule_id": "has_outgoing", "selector": { "kinds": ["anything"] } } ] }"#,
+        )
+        .unwrap();
+        assert!(validate(&p).is_empty());
+    }
+
+    // Deserializer which only records the field names of the struct asked for
+    struct FieldNames(&'static [&'static str]);
+
+    impl<'de> de::Deserializer<'de> for &mut FieldNames {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            fields: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0 = fields;
+            Err(de::Error::custom("fields recorded"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+            byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map
+            enum identifier ignored_any
+        }
+    }
+
+    fn sample(ty: ParamType) -> Value {
+        match ty {
+            ParamType::Usize => json!(1),
+            ParamType::Number => json!(0.5),
+            ParamType::String => json!("status"),
+            ParamType::Kinds | ParamType::Strings => json!(["req"]),
+            ParamType::LinkTypes => json!(["links"]),
+            ParamType::Filter => json!("status == 'open'"),
+            ParamType::KindMap => json!({ "req": ["test"] }),
+            ParamType::FieldType => json!("string"),
+            ParamType::PathPattern => json!("req -> test"),
+            ParamType::Direction => json!("forward"),
+            ParamType::KindCounts => json!({ "test": 1 }),
+            ParamType::Metric => json!("ratio"),
+            ParamType::Rule => json!({ "rule_id": "has_outgoing" }),
+        }
+    }
+
+    fn check_params<T: DeserializeOwned>(id: &str) {
+        let specs = Registry::builtins().params(id).unwrap();
+        let mut names = FieldNames(&[]);
+        let _ = T::deserialize(&mut names);
+        let mut declared: Vec<&str> = specs.iter().map(|s| s.name).collect();
+        let mut fields = names.0.to_vec();
+        declared.sort_unstable();
+        fields.sort_unstable();
+        assert_eq!(declared, fields, "params of `{id}`");
+
+        let all: serde_json::Map<String, Value> = specs
+            .iter()
+            .map(|s| (s.name.to_string(), sample(s.ty)))
+            .collect();
+        assert!(
+            serde_json::from_value::<T>(Value::Object(all.clone())).is_ok(),
+            "params of `{id}`"
+        );
+        for spec in specs {
+            let mut without = all.clone();
+            without.remove(spec.name);
+            assert_eq!(
+                serde_json::from_value::<T>(Value::Object(without)).is_err(),
+                spec.required,
+                "param `{}` of `{id}`",
+                spec.name
+            );
+        }
+    }
+
+    #[test]
+    fn param_specs_match_rule_params() {
+        type Check = fn(&str);
+        let checks: Vec<(&str, Check)> = vec![
+            ("has_outgoing", check_params::<has_outgoing::Params>),
+            ("has_incoming", check_params::<has_outgoing::Params>),
+            ("reach_kind", check_params::<reach_kind::Params>),
+            ("reach_path", check_params::<reach_path::Params>),
+            ("field_present", check_params::<field_present::Params>),
+            ("field_type", check_params::<field_type::Params>),
+            (
+                "allowed_link_types",
+                check_params::<allowed_link_types::Params>,
+            ),
+            ("no_cycles", check_params::<no_cycles::Params>),
+            ("link_targets", check_params::<link_targets::Params>),
+            ("condition", check_params::<condition::Params>),
+            ("coverage", check_params::<coverage::Params>),
+        ];
+        let mut registered: Vec<&str> = Registry::builtins().ids().collect();
+        let mut checked: Vec<&str> = checks.iter().map(|(id, _)| *id).collect();
+        registered.sort_unstable();
+        checked.sort_unstable();
+        assert_eq!(registered, checked);
+
+        for (id, check) in checks {
+            check(id);
+        }
+    }
+}
@@ -6,9 +6,11 @@
 
 use super::node::Node;
 use super::node_id::NodeId;
+use crate::error::{Error, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
 
+#[derive(Clone, Copy)]
 pub struct VersionAccessor<'source> {
     pub version: &'source str,
     pub needs: &'source HashMap<NodeId, Node>,
@@ -21,16 +23,24 @@ pub struct Dataset {
 }
 
 impl Dataset {
-    pub fn access_version<'a>(&'a self, version: &'a str) -> VersionAccessor<'a> {
-        let v = &self.versions.get(version).expect("Version not found");
-        VersionAccessor {
+    pub fn access_version<'a>(&'a self, version: &'a str) -> Result<VersionAccessor<'a>> {
+        let v = self
+            .versions
+            .get(version)
+            .ok_or_else(|| Error::MissingVersion {
+                version: Some(version.to_string()),
+            })?;
+        Ok(VersionAccessor {
             version,
             needs: &v.needs,
-        }
+        })
     }
 
-    pub fn access_current_version<'a>(&'a self) -> VersionAccessor<'a> {
-        let ver = self.current_version.as_deref().expect("Empty Optional");
+    pub fn access_current_version<'a>(&'a self) -> Result<VersionAccessor<'a>> {
+        let ver = self
+            .current_version
+            .as_deref()
+            .ok_or(Error::MissingVersion { version: None })?;
         self.access_version(ver)
     }
 }
@@ -48,8 +58,10 @@ mod tests {
     fn test_dataset_creation() {
         let mut versions = HashMap::new();
         let mut needs: HashMap<NodeId, Node> = HashMap::new();
-        let mut node = Node::default();
-        node.id = Some(NodeId::new(String::from("test")));
+        let node = Node {
+            id: Some(NodeId::new(String::from("test"))),
+            ..Default::default()
+        };
         needs.insert(NodeId::new("node1"), node);
         versions.insert("1.0.0".to_string(), VersionNode { needs });
 
@@ -101,4 +113,25 @@ mod tests {
 
         assert_eq!(dataset.versions.len(), 2);
     }
+
+    #[test]
+    fn test_access_missing_version() {
+        let dataset = Dataset {
+            current_version: None,
+            versions: HashMap::new(),
+        };
+        assert!(matches!(
+            dataset.access_current_version(),
+            Err(Error::MissingVersion { version: None })
+        ));
+
+        let dataset = Dataset {
+            current_version: Some("2.0".to_string()),
+            versions: HashMap::new(),
+        };
+        assert!(matches!(
+            dataset.access_current_version(),
+            Err(Error::MissingVersion { version: Some(v) }) if v == "2.0"
+        ));
+    }
 }
@@ -4,10 +4,12 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+use crate::error::{Error, Result};
 use crate::representation::{
-    Dataset, Issue, IssueCode, Node, NodeId, NodeKind, Severity, VersionAccessor,
+    Issue, IssueCode, LinkType, Node, NodeId, NodeKind, NodeTypes, Severity, VersionAccessor,
 };
-use std::collections::HashMap;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 
 // Keeping those values precalculated only to save time while fetching
@@ -15,13 +17,49 @@ use std::fmt;
 // production if JSON will be massive and it would need to be stored.
 // But I assume it would be not (in the demo context).
 
+// Single labelled edge. In `Graph::edges` the `target` is the linked node,
+// in `Graph::reverse_edges` it is the node the link comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub target: NodeId,
+    pub link_type: LinkType,
+}
+
+// Which way links are followed - `Forward` along them, `Backward` against them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    #[default]
+    Forward,
+    Backward,
+    Both,
+}
+
+impl Direction {
+    pub const ALL: &[&str] = &["forward", "backward", "both"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Forward => "forward",
+            Self::Backward => "backward",
+            Self::Both => "both",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Graph {
+    // Union of all link types - kept for rules which do not care about the label
     pub adjacency: HashMap<NodeId, Vec<NodeId>>,
     pub reverse: HashMap<NodeId, Vec<NodeId>>,
 
+    pub edges: HashMap<NodeId, Vec<Edge>>,
+    pub reverse_edges: HashMap<NodeId, Vec<Edge>>,
+    pub link_types: Vec<LinkType>,
+
     pub nodes: HashMap<NodeId, Node>,
     pub kinds: HashMap<NodeKind, Vec<NodeId>>,
+    pub types: NodeTypes,
 
     pub issues: Vec<Issue>,
 }
@@ -32,27 +70,56 @@ pub struct GraphIssuesView<'a>(&'a Graph);
 pub struct GraphKindsView<'a>(&'a Graph);
 
 impl Graph {
-    pub fn new(view: VersionAccessor<'_>) -> Self {
+    pub fn new(view: VersionAccessor<'_>) -> Result<Self> {
+        Self::with_link_types(view, &[])
+    }
+
+    // Link types are discovered from the export, `declared` adds the ones
+    // which can't be discovered (e.g custom exports without `*_back` fields).
+    pub fn with_link_types(view: VersionAccessor<'_>, declared: &[LinkType]) -> Result<Self> {
+        Self::with_types(view, declared, NodeTypes::default())
+    }
+
+    // Same as `with_link_types`, `types` adds the configured aliases and
+    // hierarchy. Every type used by the export becomes known as well.
+    pub fn with_types(
+        view: VersionAccessor<'_>,
+        declared: &[LinkType],
+        mut types: NodeTypes,
+    ) -> Result<Self> {
+        // Every need must have a type - otherwise it can't be bucketed by kind
+        let kinds = Self::seed_by_kind(&view, &types)?;
+        for kind in kinds.keys() {
+            types.insert(kind.clone());
+        }
         let nodes = view.needs.clone();
-        let (adjacency, mut issues) = Self::seed_adjacency(&view);
+        let link_types = Self::seed_link_types(&view, declared);
+        let (edges, mut issues) = Self::seed_edges(&view, &link_types);
 
-        let kinds = Self::seed_by_kind(&view);
+        let adjacency = Self::seed_adjacency(&edges);
         let reverse = Self::seed_reverse(&adjacency);
+        let reverse_edges = Self::seed_reverse_edges(&edges);
 
         // Validate graph consistency
         // TODO: This should maybe be outside of the constructor?
 
         issues.extend(Self::validate_consistency(&adjacency, &reverse));
-        issues.extend(Self::validate_by_kind(&nodes, &kinds));
-        issues.extend(Self::validate_dangling(&adjacency, &reverse, &nodes));
+        issues.extend(Self::validate_by_kind(&nodes, &kinds, &types));
+        issues.extend(Self::validate_dangling(
+            &adjacency, &reverse, &nodes, &types,
+        ));
 
-        Self {
+        Ok(Self {
             adjacency,
             reverse,
+            edges,
+            reverse_edges,
+            link_types,
             nodes,
             issues,
             kinds,
-        }
+            types,
+        })
     }
 
     pub fn as_adj(&self) -> GraphAdjView<'_> {
@@ -71,8 +138,32 @@ impl Graph {
     pub fn edges_len(&self) -> usize {
         self.adjacency.values().map(|v| v.len()).sum()
     }
-    pub fn of_kind(&self, k: crate::representation::NodeKind) -> &[crate::representation::NodeId] {
-        self.kinds.get(&k).map(|v| v.as_slice()).unwrap_or(&[])
+    pub fn of_kind(&self, k: &NodeKind) -> &[NodeId] {
+        self.kinds
+            .get(&self.types.resolve(k.as_str()))
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+    // Needs of `k` and of all its subtypes, sorted
+    pub fn of_type(&self, k: &NodeKind) -> Vec<&NodeId> {
+        let mut out: Vec<&NodeId> = self
+            .kinds
+            .iter()
+            .filter(|(kind, _)| self.types.is_a(kind, k))
+            .flat_map(|(_, ids)| ids)
+            .collect();
+        out.sort();
+        out
+    }
+    // Canonical type of a need, aliases resolved
+    pub fn kind_of(&self, id: &NodeId) -> Option<NodeKind> {
+        let raw = self.nodes.get(id)?.kind.as_deref()?;
+        Some(self.types.resolve(raw))
+    }
+    // Whether the need is of type `k` or of one of its subtypes
+    pub fn is_a(&self, id: &NodeId, k: &NodeKind) -> bool {
+        self.kind_of(id)
+            .is_some_and(|kind| self.types.is_a(&kind, k))
     }
     pub fn out(&self, id: &crate::representation::NodeId) -> &[crate::representation::NodeId] {
         self.adjacency.get(id).map(|v| v.as_slice()).unwrap_or(&[])
@@ -80,34 +171,249 @@ impl Graph {
     pub fn inc(&self, id: &crate::representation::NodeId) -> &[crate::representation::NodeId] {
         self.reverse.get(id).map(|v| v.as_slice()).unwrap_or(&[])
     }
+    pub fn out_edges(&self, id: &NodeId) -> &[Edge] {
+        self.edges.get(id).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+    pub fn inc_edges(&self, id: &NodeId) -> &[Edge] {
+        self.reverse_edges
+            .get(id)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+    // Same as `out` but restricted to the given link types, empty means any.
+    pub fn out_via(&self, id: &NodeId, link_types: &[LinkType]) -> Vec<&NodeId> {
+        if link_types.is_empty() {
+            return self.out(id).iter().collect();
+        }
+        Self::filter_edges(self.out_edges(id), link_types)
+    }
+    // Same as `inc` but restricted to the given link types, empty means any.
+    pub fn inc_via(&self, id: &NodeId, link_types: &[LinkType]) -> Vec<&NodeId> {
+        if link_types.is_empty() {
+            return self.inc(id).iter().collect();
+        }
+        Self::filter_edges(self.inc_edges(id), link_types)
+    }
+    // Neighbours in the given direction restricted to the link types, empty
+    // means any. With `Both` a need linked both ways is listed once.
+    pub fn via(&self, id: &NodeId, link_types: &[LinkType], direction: Direction) -> Vec<&NodeId> {
+        match direction {
+            Direction::Forward => self.out_via(id, link_types),
+            Direction::Backward => self.inc_via(id, link_types),
+            Direction::Both => {
+                let mut out = self.out_via(id, link_types);
+                for n in self.inc_via(id, link_types) {
+                    if !out.contains(&n) {
+                        out.push(n);
+                    }
+                }
+                out
+            }
+        }
+    }
+
+    // Needs reachable from `from` with their distance (number of links on
+    // the shortest path), up to `max_hops` when given. The start needs are
+    // not listed. Sorted by distance, then by id.
+    pub fn reachable<'a>(
+        &'a self,
+        from: &[&'a NodeId],
+        link_types: &[LinkType],
+        direction: Direction,
+        max_hops: Option<usize>,
+    ) -> Vec<(&'a NodeId, usize)> {
+        let mut seen: HashSet<&NodeId> = from.iter().copied().collect();
+        let mut frontier: Vec<&NodeId> = from.to_vec();
+        let mut out = Vec::new();
+        let mut distance = 0;
+
+        while !frontier.is_empty() && max_hops.is_none_or(|max| distance < max) {
+            distance += 1;
+            let mut next = Vec::new();
+            for id in frontier {
+                for nxt in self.via(id, link_types, direction) {
+                    if seen.insert(nxt) {
+                        next.push(nxt);
+                    }
+                }
+            }
+            next.sort();
+            out.extend(next.iter().map(|id| (*id, distance)));
+            frontier = next;
+        }
+        out
+    }
+    // Strongly connected components (Tarjan) of the subgraph induced by
+    // `within`, following only `link_types` (empty means any). Components
+    // are sorted by id and listed in the order of their smallest member.
+    pub fn strongly_connected<'a>(
+        &'a self,
+        within: &[&'a NodeId],
+        link_types: &[LinkType],
+    ) -> Vec<Vec<&'a NodeId>> {
+        let members: HashSet<&NodeId> = within.iter().copied().collect();
+        let succ = |id: &'a NodeId| -> Vec<&'a NodeId> {
+            self.out_via(id, link_types)
+                .into_iter()
+                .filter(|n| members.contains(n))
+                .collect()
+        };
+
+        let mut roots: Vec<&NodeId> = members.iter().copied().collect();
+        roots.sort();
+
+        let mut index: HashMap<&NodeId, usize> = HashMap::new();
+        let mut low: HashMap<&NodeId, usize> = HashMap::new();
+        let mut stack: Vec<&NodeId> = Vec::new();
+        let mut on_stack: HashSet<&NodeId> = HashSet::new();
+        let mut out = Vec::new();
+
+        for root in roots {
+            if index.contains_key(root) {
+                continue;
+            }
+            // Explicit call stack: node, its successors and the next one to visit
+            let mut work: Vec<(&NodeId, Vec<&NodeId>, usize)> = Vec::new();
+            let mut next = Some(root);
+
+            loop {
+                if let Some(id) = next.take() {
+                    let i = index.len();
+                    index.insert(id, i);
+                    low.insert(id, i);
+                    stack.push(id);
+                    on_stack.insert(id);
+                    work.push((id, succ(id), 0));
+                }
+                let Some(top) = work.last_mut() else {
+                    break;
+                };
+                let v = top.0;
+                if let Some(&w) = top.1.get(top.2) {
+                    top.2 += 1;
+                    if !index.contains_key(w) {
+                        next = Some(w);
+                    } else if on_stack.contains(w) {
+                        let lw = index[w].min(low[v]);
+                        low.insert(v, lw);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some(parent) = work.last() {
+                    let lp = low[parent.0].min(low[v]);
+                    low.insert(parent.0, lp);
+                }
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack.remove(w);
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort();
+                    out.push(component);
+                }
+            }
+        }
+
+        out.sort();
+        out
+    }
     pub fn has_errors(&self) -> bool {
         self.issues.iter().any(|i| i.severity == Severity::Error)
     }
 
-    fn seed_adjacency(view: &VersionAccessor<'_>) -> (HashMap<NodeId, Vec<NodeId>>, Vec<Issue>) {
-        let mut adj: HashMap<NodeId, Vec<NodeId>> = HashMap::with_capacity(view.needs.len());
+    fn filter_edges<'a>(edges: &'a [Edge], link_types: &[LinkType]) -> Vec<&'a NodeId> {
+        let mut seen = HashSet::new();
+        edges
+            .iter()
+            .filter(|e| link_types.contains(&e.link_type))
+            .filter(|e| seen.insert(&e.target))
+            .map(|e| &e.target)
+            .collect()
+    }
+
+    fn seed_link_types(view: &VersionAccessor<'_>, declared: &[LinkType]) -> Vec<LinkType> {
+        let mut types: BTreeSet<LinkType> = declared.iter().cloned().collect();
+        types.insert(LinkType::links());
+        for node in view.needs.values() {
+            types.extend(node.link_types());
+        }
+        types.into_iter().collect()
+    }
+
+    fn seed_edges(
+        view: &VersionAccessor<'_>,
+        link_types: &[LinkType],
+    ) -> (HashMap<NodeId, Vec<Edge>>, Vec<Issue>) {
+        let mut edges: HashMap<NodeId, Vec<Edge>> = HashMap::with_capacity(view.needs.len());
         let mut issues: Vec<Issue> = Vec::new();
 
         for (current_id, node) in view.needs.iter() {
-            let mut visited = std::collections::HashSet::new();
             let mut linked = Vec::with_capacity(node.links.len());
 
-            for linked_id in &node.links {
-                if visited.insert(linked_id) {
-                    linked.push(NodeId(linked_id.clone()));
-                } else {
-                    // Duplicated link
-                    issues.push(Issue::warn(
-                        IssueCode::DuplicateLink,
-                        current_id.clone(),
-                        format!("duplicate link {} -> {}", current_id, linked_id),
-                    ));
+            for link_type in link_types {
+                let mut visited = HashSet::new();
+                for linked_id in node.linked(link_type) {
+                    if visited.insert(linked_id) {
+                        linked.push(Edge {
+                            target: NodeId::from(linked_id),
+                            link_type: link_type.clone(),
+                        });
+                    } else {
+                        // Duplicated link
+                        issues.push(Issue::warn(
+                            IssueCode::DuplicateLink,
+                            current_id.clone(),
+                            format!(
+                                "duplicate {} link {} -> {}",
+                                link_type, current_id, linked_id
+                            ),
+                        ));
+                    }
                 }
             }
-            adj.insert(current_id.clone(), linked);
+            edges.insert(current_id.clone(), linked);
+        }
+
+        (edges, issues)
+    }
+
+    fn seed_adjacency(edges: &HashMap<NodeId, Vec<Edge>>) -> HashMap<NodeId, Vec<NodeId>> {
+        let mut adj: HashMap<NodeId, Vec<NodeId>> = HashMap::with_capacity(edges.len());
+
+        for (current_id, linked) in edges {
+            // The same pair might be linked through several link types
+            let mut visited = HashSet::new();
+            let targets = linked
+                .iter()
+                .filter(|e| visited.insert(&e.target))
+                .map(|e| e.target.clone())
+                .collect();
+            adj.insert(current_id.clone(), targets);
+        }
+
+        adj
+    }
+
+    fn seed_reverse_edges(edges: &HashMap<NodeId, Vec<Edge>>) -> HashMap<NodeId, Vec<Edge>> {
+        let mut rev: HashMap<NodeId, Vec<Edge>> = HashMap::new();
+
+        for (current_id, linked) in edges {
+            for edge in linked {
+                rev.entry(edge.target.clone()).or_default().push(Edge {
+                    target: current_id.clone(),
+                    link_type: edge.link_type.clone(),
+                });
+            }
+            rev.entry(current_id.clone()).or_default();
         }
 
-        (adj, issues)
+        rev
     }
 
     fn seed_reverse(adj: &HashMap<NodeId, Vec<NodeId>>) -> HashMap<NodeId, Vec<NodeId>> {
@@ -125,17 +431,24 @@ impl Graph {
         rev
     }
 
-    fn seed_by_kind(view: &VersionAccessor<'_>) -> HashMap<NodeKind, Vec<NodeId>> {
+    fn seed_by_kind(
+        view: &VersionAccessor<'_>,
+        types: &NodeTypes,
+    ) -> Result<HashMap<NodeKind, Vec<NodeId>>> {
         let mut by_kind: HashMap<NodeKind, Vec<NodeId>> = HashMap::new();
 
         for (id, node) in view.needs {
+            let kind = node
+                .kind
+                .as_deref()
+                .ok_or_else(|| Error::MissingType { id: id.clone() })?;
             by_kind
-                .entry(NodeKind::from_str(node.kind.as_deref().unwrap()))
+                .entry(types.resolve(kind))
                 .or_default()
                 .push(id.clone());
         }
 
-        by_kind
+        Ok(by_kind)
     }
 
     fn validate_consistency(
@@ -204,12 +517,13 @@ impl Graph {
     fn validate_by_kind(
         by_id: &HashMap<NodeId, Node>,
         by_kind: &HashMap<NodeKind, Vec<NodeId>>,
+        types: &NodeTypes,
     ) -> Vec<Issue> {
         let mut issues = Vec::new();
 
         // Check if there are no duplicated nodes between Node types
         for (id, node) in by_id {
-            let kind = NodeKind::from_str(node.kind.as_deref().expect("Kind missing"));
+            let kind = types.resolve(node.kind.as_deref().unwrap_or_default());
 
             match by_kind.get(&kind) {
                 None => issues.push(Issue::error(
@@ -243,22 +557,25 @@ impl Graph {
         adj: &HashMap<NodeId, Vec<NodeId>>,
         rev: &HashMap<NodeId, Vec<NodeId>>,
         nodes: &HashMap<NodeId, Node>,
+        types: &NodeTypes,
     ) -> Vec<Issue> {
         let mut issues = Vec::new();
         for id in adj.keys() {
-            // Some nodes most likely would never have links e.g (NodeKind::Person | NodeKind::Team)
+            // Some nodes most likely would never have links e.g (person | team)
             // just exclude them and continue
-            if let Some(node) = nodes.get(id) {
-                if matches!(
-                    NodeKind::from_str(node.kind.as_deref().unwrap()),
-                    NodeKind::Person | NodeKind::Team
-                ) {
-                    continue;
-                }
+            if let Some(node) = nodes.get(id)
+                && matches!(
+                    types
+                        .resolve(node.kind.as_deref().unwrap_or_default())
+                        .as_str(),
+                    "person" | "team"
+                )
+            {
+                continue;
             }
 
-            let outs_empty = adj.get(id).map_or(true, |v| v.is_empty());
-            let ins_empty = rev.get(id).map_or(true, |v| v.is_empty());
+            let outs_empty = adj.get(id).is_none_or(|v| v.is_empty());
+            let ins_empty = rev.get(id).is_none_or(|v| v.is_empty());
             if outs_empty && ins_empty {
                 issues.push(Issue::suggest(
                     IssueCode::DandlingNode,
@@ -305,7 +622,7 @@ impl fmt::Display for GraphKindsView<'_> {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(formatter, "Kinds:")?;
         for (kind, ids) in &self.0.kinds {
-            writeln!(formatter, "  {kind:?}: {:?}", ids)?;
+            writeln!(formatter, "  {kind}: {:?}", ids)?;
         }
         Ok(())
     }
@@ -376,10 +693,25 @@ mod tests {
       }
     }"#;
 
+    // typed links: IMPL_1 implements SWREQ_1 and links REQ_1
+    const JSON_TYPED: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "REQ_1":   { "id":"REQ_1","type":"req","links":[],"implements":[],"implements_back":[] },
+            "SWREQ_1": { "id":"SWREQ_1","type":"swreq","links":[],"implements":[],"implements_back":["IMPL_1"] },
+            "IMPL_1":  { "id":"IMPL_1","type":"impl","links":["REQ_1"],"implements":["SWREQ_1"],"implements_back":[] }
+          }
+        }
+      }
+    }"#;
+
     fn build_graph(json: &str) -> Graph {
         let ds: Dataset = populate_from_str(json).expect("parse json");
-        let view: VersionAccessor<'_> = ds.access_current_version();
-        Graph::new(view)
+        let view: VersionAccessor<'_> = ds.access_current_version().expect("current version");
+        Graph::new(view).expect("build graph")
     }
 
     // ---- Tests ------------------------------------------------------------
@@ -402,7 +734,7 @@ mod tests {
         assert_eq!(g.inc(&NodeId::from("TEST_1")), &[NodeId::from("SPEC_1")]);
 
         // by_kind buckets
-        let reqs = g.of_kind(NodeKind::Req);
+        let reqs = g.of_kind(&NodeKind::from("req"));
         assert_eq!(reqs, &[NodeId::from("REQ_1")]);
 
         // no issues
@@ -463,7 +795,7 @@ mod tests {
         for (id, n) in &g.nodes {
             let bucket = g
                 .kinds
-                .get(&NodeKind::from_str(&n.kind.as_deref().unwrap()))
+                .get(&NodeKind::from_str(n.kind.as_deref().unwrap()))
                 .expect("bucket for kind");
             assert!(
                 bucket.iter().any(|x| x == id),
@@ -487,4 +819,135 @@ mod tests {
         });
         assert!(saw_isolated, "expected suggestion for isolated node");
     }
+
+    #[test]
+    fn aliases_of_people_are_not_dangling() {
+        let ds: Dataset = populate_from_str(
+            r#"{ "current_version": "1.0", "versions": { "1.0": { "needs": {
+                "ALICE": { "id":"ALICE","type":"engineer","links":[] },
+                "LONE": { "id":"LONE","type":"req","links":[] }
+            } } } }"#,
+        )
+        .expect("parse json");
+        let defs = serde_json::from_str(r#"{ "person": { "aliases": ["engineer"] } }"#).unwrap();
+        let g = Graph::with_types(
+            ds.access_current_version().expect("current version"),
+            &[],
+            NodeTypes::new(&defs),
+        )
+        .expect("build graph");
+
+        let dangling: Vec<&NodeId> = g
+            .issues
+            .iter()
+            .filter(|i| i.code == IssueCode::DandlingNode)
+            .map(|i| &i.subject)
+            .collect();
+        assert_eq!(dangling, vec![&NodeId::from("LONE")]);
+    }
+
+    #[test]
+    fn typed_edges_are_discovered_from_back_pairs() {
+        let g = build_graph(JSON_TYPED);
+        let implements = LinkType::from("implements");
+
+        assert_eq!(g.link_types, vec![implements.clone(), LinkType::links()]);
+        assert_eq!(g.edges_len(), 2);
+
+        let impl_id = NodeId::from("IMPL_1");
+        assert_eq!(
+            g.out_via(&impl_id, std::slice::from_ref(&implements)),
+            vec![&NodeId::from("SWREQ_1")]
+        );
+        assert_eq!(
+            g.out_via(&impl_id, &[LinkType::links()]),
+            vec![&NodeId::from("REQ_1")]
+        );
+        assert_eq!(g.out_via(&impl_id, &[]).len(), 2);
+
+        assert_eq!(
+            g.inc_via(&NodeId::from("SWREQ_1"), &[implements]),
+            vec![&impl_id]
+        );
+        assert!(
+            g.inc_via(&NodeId::from("REQ_1"), &[LinkType::from("implements")])
+                .is_empty()
+        );
+        assert!(!g.has_errors());
+    }
+
+    #[test]
+    fn declared_link_types_are_used_without_back_pairs() {
+        let json = r#"
+        {
+          "current_version": "1.0",
+          "versions": {
+            "1.0": {
+              "needs": {
+                "TEST_1": { "id":"TEST_1","type":"test","verifies":["REQ_1"] },
+                "REQ_1":  { "id":"REQ_1","type":"req" }
+              }
+            }
+          }
+        }"#;
+        let ds: Dataset = populate_from_str(json).expect("parse json");
+        let verifies = LinkType::from("verifies");
+
+        let undeclared = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        assert_eq!(undeclared.edges_len(), 0);
+
+        let g = Graph::with_link_types(
+            ds.access_current_version().unwrap(),
+            std::slice::from_ref(&verifies),
+        )
+        .unwrap();
+        assert_eq!(
+            g.out_via(&NodeId::from("TEST_1"), &[verifies]),
+            vec![&NodeId::from("REQ_1")]
+        );
+    }
+
+    #[test]
+    fn missing_type_is_an_error() {
+        let json = r#"
+        {
+          "current_version": "1.0",
+          "versions": {
+            "1.0": {
+              "needs": {
+                "REQ_1": { "id":"REQ_1","links":[] }
+              }
+            }
+          }
+        }"#;
+        let ds: Dataset = populate_from_str(json).expect("parse json");
+
+        let err = Graph::new(ds.access_current_version().unwrap()).unwrap_err();
+        assert!(matches!(err, Error::MissingType { id } if id == NodeId::from("REQ_1")));
+    }
+
+    #[test]
+    fn reachable_reports_shortest_distances() {
+        let g = build_graph(JSON_OK);
+        let req = NodeId::from("REQ_1");
+        let test = NodeId::from("TEST_1");
+        let spec = NodeId::from("SPEC_1");
+
+        assert_eq!(
+            g.reachable(&[&req], &[], Direction::Forward, None),
+            vec![(&spec, 1), (&test, 2)]
+        );
+        assert_eq!(
+            g.reachable(&[&req], &[], Direction::Forward, Some(1)),
+            vec![(&spec, 1)]
+        );
+        assert_eq!(
+            g.reachable(&[&test], &[], Direction::Backward, None),
+            vec![(&spec, 1), (&req, 2)]
+        );
+        assert!(
+            g.reachable(&[&test], &[], Direction::Forward, None)
+                .is_empty()
+        );
+    }
 }
@@ -8,7 +8,7 @@ use super::node_id::NodeId;
 use std::fmt;
 use strum_macros::IntoStaticStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, IntoStaticStr, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, IntoStaticStr)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum IssueCode {
     ImplNoLinks,
@@ -24,7 +24,15 @@ pub enum IssueCode {
     MissingAuthor,
     MissingTeam,
     DandlingNode,
-    Unknown,
+    WaiverExpired,
+    WaiverUnused,
+    WaiverInvalid,
+    Cycle,
+    LinkTarget,
+    SuspectLink,
+    // Code of a policy rule without a built-in meaning, kept as written,
+    // e.g `REQ_STATUS` or `metamodel.req.asil.type`
+    Rule(String),
 }
 
 impl IssueCode {
@@ -46,6 +54,12 @@ impl IssueCode {
             "PENDING_IMPL" => IssueCode::PendingImpl,
             "DANGLING_NODE" => IssueCode::DanglingNode,
             "DANDLING_NODE" => IssueCode::DandlingNode,
+            "WAIVER_EXPIRED" => IssueCode::WaiverExpired,
+            "WAIVER_UNUSED" => IssueCode::WaiverUnused,
+            "WAIVER_INVALID" => IssueCode::WaiverInvalid,
+            "CYCLE" | "NO_CYCLES" => IssueCode::Cycle,
+            "LINK_TARGET" | "LINK_TARGETS" => IssueCode::LinkTarget,
+            "SUSPECT_LINK" => IssueCode::SuspectLink,
 
             "IMPL_MUST_LINK_SOMETHING" => IssueCode::ImplNoLinks,
             "REQ_MUST_HAVE_DIRECT_TEST" => IssueCode::ReqMissingDirectTest,
@@ -54,11 +68,11 @@ impl IssueCode {
             "IMPL_STATUS_REQUIRED" => IssueCode::ImplLacksStatus,
             "PR_NOT_MERGED_BY_AUTHOR" => IssueCode::MergeByAuthor,
 
-            _ => IssueCode::Unknown,
+            _ => IssueCode::Rule(code.as_ref().to_string()),
         }
     }
 
-    pub fn to_str(&self) -> &'static str {
+    pub fn to_str(&self) -> &str {
         match self {
             IssueCode::ImplNoLinks => "impl_no_links",
             IssueCode::ReqMissingDirectTest => "req_missing_direct_test",
@@ -73,18 +87,33 @@ impl IssueCode {
             IssueCode::MissingAuthor => "missing_author",
             IssueCode::MissingTeam => "missing_team",
             IssueCode::DandlingNode => "dandling_node",
-            IssueCode::Unknown => "unknown",
+            IssueCode::WaiverExpired => "waiver_expired",
+            IssueCode::WaiverUnused => "waiver_unused",
+            IssueCode::WaiverInvalid => "waiver_invalid",
+            IssueCode::Cycle => "cycle",
+            IssueCode::LinkTarget => "link_target",
+            IssueCode::SuspectLink => "suspect_link",
+            IssueCode::Rule(code) => code,
         }
     }
 }
 
+impl serde::Serialize for IssueCode {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.to_str())
+    }
+}
+
 impl fmt::Display for IssueCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.to_str())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+// Ordered from the least to the most severe
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Suggestion,
@@ -108,12 +137,23 @@ impl std::fmt::Display for Severity {
     }
 }
 
-#[derive(Debug, serde::Serialize)]
+// Subject of issues about the project as a whole rather than a single need,
+// e.g. a coverage threshold which is not met
+pub const PROJECT_SUBJECT: &str = "(project)";
+
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Issue {
     pub severity: Severity,
     pub code: IssueCode,
     pub subject: NodeId,
     pub detail: String,
+    // Other needs involved, e.g. the members of a cycle
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<NodeId>,
+    // Identifies the issue in place of `detail` when the detail varies while
+    // the issue persists, e.g. `reach_kind` lists the targets it reached
+    #[serde(skip)]
+    pub key: Option<String>,
 }
 
 impl Issue {
@@ -123,6 +163,8 @@ impl Issue {
             code,
             subject,
             detail: detail.into(),
+            related: Vec::new(),
+            key: None,
         }
     }
 
@@ -132,6 +174,8 @@ impl Issue {
             code,
             subject,
             detail: detail.into(),
+            related: Vec::new(),
+            key: None,
         }
     }
 
@@ -141,10 +185,54 @@ impl Issue {
             code,
             subject,
             detail: detail.into(),
+            related: Vec::new(),
+            key: None,
+        }
+    }
+
+    pub fn with_related(mut self, related: Vec<NodeId>) -> Self {
+        self.related = related;
+        self
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+}
+
+// Identity of an issue across runs, e.g to match the issues of two exports.
+// `code` is the policy rule code for policy issues and the issue code for
+// issues found while building the graph. `detail` is the issue's `key` when
+// it has one, so a partial fix doesn't turn a known issue into a new one.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub struct IssueKey {
+    pub code: String,
+    pub subject: NodeId,
+    pub detail: String,
+}
+
+impl IssueKey {
+    pub fn new(code: &str, issue: &Issue) -> Self {
+        Self {
+            code: code.to_ascii_lowercase(),
+            subject: issue.subject.clone(),
+            detail: normalize(issue.key.as_deref().unwrap_or(&issue.detail)),
         }
     }
 }
 
+// Whitespace and case differences should not turn a known issue into a new one
+fn normalize(detail: &str) -> String {
+    detail
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +244,12 @@ mod tests {
         assert_eq!(Severity::Suggestion.to_str(), "suggestion");
     }
 
+    #[test]
+    fn severity_ordering() {
+        assert!(Severity::Suggestion < Severity::Warning);
+        assert!(Severity::Warning < Severity::Error);
+    }
+
     #[test]
     fn issue_creation() {
         let issue = Issue {
@@ -163,6 +257,8 @@ mod tests {
             code: IssueCode::BrokenLink,
             subject: NodeId::new("1"),
             detail: "Test error".to_string(),
+            related: Vec::new(),
+            key: None,
         };
 
         assert_eq!(issue.severity, Severity::Error);
@@ -170,4 +266,41 @@ mod tests {
         assert_eq!(issue.subject, NodeId::new("1"));
         assert_eq!(issue.detail, "Test error");
     }
+
+    #[test]
+    fn issue_key_ignores_case_and_whitespace() {
+        let a = Issue::warn(IssueCode::BrokenLink, NodeId::new("A"), "edge A ->  X");
+        let b = Issue::error(IssueCode::BrokenLink, NodeId::new("A"), " Edge a -> x");
+        assert_eq!(
+            IssueKey::new("BROKEN_LINK", &a),
+            IssueKey::new("broken_link", &b)
+        );
+        assert_ne!(IssueKey::new("broken_link", &a), IssueKey::new("other", &b));
+    }
+
+    #[test]
+    fn issue_serializes_with_snake_case_code() {
+        let issue = Issue::warn(IssueCode::DuplicateLink, NodeId::new("1"), "dup");
+        let json = serde_json::to_value(&issue).unwrap();
+
+        assert_eq!(json["code"], IssueCode::DuplicateLink.to_str());
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["subject"], "1");
+    }
+
+    #[test]
+    fn rule_codes_without_a_variant_are_kept() {
+        assert_eq!(IssueCode::from_rule_code("no_cycles"), IssueCode::Cycle);
+        let code = IssueCode::from_rule_code("metamodel.req.asil.type");
+        assert_eq!(code, IssueCode::Rule("metamodel.req.asil.type".into()));
+        assert_eq!(code.to_string(), "metamodel.req.asil.type");
+
+        let issue = Issue::error(
+            IssueCode::from_rule_code("REQ_STATUS"),
+            NodeId::new("1"),
+            "x",
+        );
+        let json = serde_json::to_value(&issue).unwrap();
+        assert_eq!(json["code"], "REQ_STATUS");
+    }
 }
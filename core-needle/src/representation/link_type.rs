@@ -0,0 +1,83 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+
+// Sphinx-Needs exports every link option twice - once as the forward list
+// (e.g `implements`) and once as the computed backward list (`implements_back`).
+// That pair is what we use to tell link options apart from any other list field.
+pub const BACK_SUFFIX: &str = "_back";
+
+// Name of the default link option, always present in the export.
+pub const DEFAULT_LINK_TYPE: &str = "links";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct LinkType(pub String);
+
+impl LinkType {
+    pub fn new<S: Into<String>>(s: S) -> Self {
+        Self(s.into())
+    }
+
+    pub fn links() -> Self {
+        Self::new(DEFAULT_LINK_TYPE)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn back_field(&self) -> String {
+        format!("{}{}", self.0, BACK_SUFFIX)
+    }
+}
+
+impl AsRef<str> for LinkType {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for LinkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for LinkType {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for LinkType {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_type_default() {
+        assert_eq!(LinkType::links().as_str(), "links");
+    }
+
+    #[test]
+    fn test_link_type_back_field() {
+        assert_eq!(LinkType::from("implements").back_field(), "implements_back");
+    }
+
+    #[test]
+    fn test_link_type_deserialize_from_string() {
+        let lt: Vec<LinkType> = serde_json::from_str(r#"["reqs", "specs"]"#).unwrap();
+        assert_eq!(lt, vec![LinkType::from("reqs"), LinkType::from("specs")]);
+    }
+}
@@ -8,14 +8,18 @@ pub mod dataset;
 pub mod graph;
 pub mod graph_data_traits;
 pub mod issue;
+pub mod link_type;
 pub mod node;
 pub mod node_id;
 pub mod node_kind;
+pub mod node_types;
 
 pub use dataset::{Dataset, VersionAccessor, VersionNode};
-pub use graph::{Graph, GraphAdjView, GraphIssuesView, GraphKindsView};
+pub use graph::{Direction, Edge, Graph, GraphAdjView, GraphIssuesView, GraphKindsView};
 pub use graph_data_traits::Identifiable;
-pub use issue::{Issue, IssueCode, Severity};
+pub use issue::{Issue, IssueCode, IssueKey, PROJECT_SUBJECT, Severity};
+pub use link_type::LinkType;
 pub use node::Node;
 pub use node_id::NodeId;
 pub use node_kind::NodeKind;
+pub use node_types::{NodeTypes, TypeDef};
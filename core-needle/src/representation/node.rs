@@ -4,10 +4,10 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
+use super::link_type::{BACK_SUFFIX, DEFAULT_LINK_TYPE, LinkType};
 use super::node_id::NodeId;
 use serde::Deserialize;
 use serde_json::Value;
-use serde_json::json;
 use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -67,7 +67,75 @@ impl Node {
             "tags" => self.tags.as_ref().map(|v| !v.is_empty()).unwrap_or(false),
             "links" => !self.links.is_empty(),
             "links_back" => !self.links_back.is_empty(),
-            other => self.extra.get(other).map_or(false, json_value_present),
+            other => self.extra.get(other).is_some_and(json_value_present),
+        }
+    }
+
+    // Value of any need option, typed fields are converted back to the JSON
+    // they were read from so policies can treat every option the same way.
+    pub fn field_value(&self, key: &str) -> Option<Value> {
+        let strings = |v: &[String]| Value::from(v.to_vec());
+        match key {
+            "id" => self.id.as_ref().map(|id| Value::from(id.as_ref())),
+            "type" | "kind" => self.kind.clone().map(Value::from),
+            "title" => self.title.clone().map(Value::from),
+            "status" => self.status.clone().map(Value::from),
+            "url" => self.url.clone().map(Value::from),
+            "tags" => self.tags.as_deref().map(strings),
+            "links" => Some(strings(&self.links)),
+            "links_back" => Some(strings(&self.links_back)),
+            other => self.extra.get(other).cloned(),
+        }
+    }
+
+    // Sphinx document the need is defined in, without the suffix
+    pub fn docname(&self) -> Option<&str> {
+        self.extra.get("docname").and_then(Value::as_str)
+    }
+
+    // Source document of the need relative to the Sphinx source dir, e.g
+    // `automotive-adas/index.rst` (`docname` + `doctype` from the export).
+    pub fn doc_path(&self) -> Option<String> {
+        let docname = self.docname()?;
+        if docname.trim().is_empty() {
+            return None;
+        }
+        let doctype = self
+            .extra
+            .get("doctype")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        Some(format!("{docname}{doctype}"))
+    }
+
+    pub fn lineno(&self) -> Option<u64> {
+        self.extra.get("lineno").and_then(Value::as_u64)
+    }
+
+    // Link options present on this node. `links` is always there, every other
+    // option is recognised by its `<name>_back` counterpart in the export.
+    pub fn link_types(&self) -> Vec<LinkType> {
+        let mut out = vec![LinkType::links()];
+        for key in self.extra.keys() {
+            if let Some(base) = key.strip_suffix(BACK_SUFFIX)
+                && !base.is_empty()
+                && self.extra.get(base).is_some_and(Value::is_array)
+            {
+                out.push(LinkType::from(base));
+            }
+        }
+        out
+    }
+
+    // Targets of the given link option, `links` is read from the typed field
+    // and any other option from `extra`.
+    pub fn linked(&self, link_type: &LinkType) -> Vec<&str> {
+        if link_type.as_str() == DEFAULT_LINK_TYPE {
+            return self.links.iter().map(String::as_str).collect();
+        }
+        match self.extra.get(link_type.as_str()) {
+            Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
         }
     }
 }
@@ -85,6 +153,7 @@ fn json_value_present(v: &Value) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_node_default() {
@@ -126,4 +195,68 @@ mod tests {
         );
         assert_eq!(node.extra["custom_field"], json!("custom_value"));
     }
+
+    #[test]
+    fn test_node_source_location() {
+        let json = r#"{
+            "id": "REQ_1",
+            "type": "req",
+            "docname": "automotive-adas/requirements",
+            "doctype": ".rst",
+            "lineno": 42
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            node.doc_path().as_deref(),
+            Some("automotive-adas/requirements.rst")
+        );
+        assert_eq!(node.lineno(), Some(42));
+        assert!(Node::default().doc_path().is_none());
+    }
+
+    #[test]
+    fn test_node_field_value() {
+        let json = r#"{
+            "id": "REQ_1",
+            "type": "req",
+            "status": "open",
+            "tags": ["safety"],
+            "asil": "B"
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert_eq!(node.field_value("id"), Some(json!("REQ_1")));
+        assert_eq!(node.field_value("status"), Some(json!("open")));
+        assert_eq!(node.field_value("tags"), Some(json!(["safety"])));
+        assert_eq!(node.field_value("links"), Some(json!([])));
+        assert_eq!(node.field_value("asil"), Some(json!("B")));
+        assert_eq!(node.field_value("title"), None);
+    }
+
+    #[test]
+    fn test_node_link_types_from_back_pairs() {
+        let json = r#"{
+            "id": "IMPL_1",
+            "type": "impl",
+            "links": ["REQ_1"],
+            "implements": ["SWREQ_1", "SWREQ_2"],
+            "implements_back": [],
+            "tags_back": "not a link option",
+            "author": ["ALFRED"]
+        }"#;
+
+        let node: Node = serde_json::from_str(json).unwrap();
+        let mut types = node.link_types();
+        types.sort();
+
+        assert_eq!(types, vec![LinkType::from("implements"), LinkType::links()]);
+        assert_eq!(node.linked(&LinkType::links()), vec!["REQ_1"]);
+        assert_eq!(
+            node.linked(&LinkType::from("implements")),
+            vec!["SWREQ_1", "SWREQ_2"]
+        );
+        assert_eq!(node.linked(&LinkType::from("author")), vec!["ALFRED"]);
+        assert!(node.linked(&LinkType::from("specs")).is_empty());
+    }
 }
@@ -4,113 +4,84 @@
 // SPDX-License-Identifier: MIT
 // -----------------------------------------------------------------------------
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 
-// TODO: use strum to cut down the boilerplate
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Copy)]
-#[serde(rename_all = "lowercase")]
-pub enum NodeKind {
-    Person,
-    Team,
-    Arch,
-    Swarch,
-    Req,
-    Swreq,
-    Spec,
-    Test,
-    Testsuite,
-    Testrun,
-    Impl,
-    Need,
-    Release,
-
-    #[serde(other)]
-    Unknown,
-}
+// Need type as used by the export (`type` of a need). Types are open - every
+// project defines its own - so this is only the normalized name, what a type
+// means is described by `NodeTypes`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct NodeKind(String);
 
 impl NodeKind {
+    pub fn new<S: AsRef<str>>(raw: S) -> Self {
+        Self(raw.as_ref().trim().to_lowercase())
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(raw: &str) -> Self {
-        match raw.trim().to_lowercase().as_str() {
-            "person" => Self::Person,
-            "team" => Self::Team,
-            "arch" => Self::Arch,
-            "swarch" => Self::Swarch,
-            "req" => Self::Req,
-            "swreq" => Self::Swreq,
-            "spec" => Self::Spec,
-            "test" => Self::Test,
-            "testsuite" => Self::Testsuite,
-            "testrun" => Self::Testrun,
-            "impl" => Self::Impl,
-            "need" => Self::Need,
-            "release" => Self::Release,
-            _ => Self::Unknown,
-        }
+        Self::new(raw)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Person => "person",
-            Self::Team => "team",
-            Self::Arch => "arch",
-            Self::Swarch => "swarch",
-            Self::Req => "req",
-            Self::Swreq => "swreq",
-            Self::Spec => "spec",
-            Self::Test => "test",
-            Self::Testsuite => "testsuite",
-            Self::Testrun => "testrun",
-            Self::Impl => "impl",
-            Self::Need => "need",
-            Self::Release => "release",
-            Self::Unknown => "unknown",
-        }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<str> for NodeKind {
+    fn as_ref(&self) -> &str {
+        &self.0
     }
 }
 
 impl fmt::Display for NodeKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.as_str())
+        f.write_str(&self.0)
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_from_str_valid_inputs() {
-        assert_eq!(NodeKind::from_str("person"), NodeKind::Person);
-        assert_eq!(NodeKind::from_str("team"), NodeKind::Team);
-        assert_eq!(NodeKind::from_str("arch"), NodeKind::Arch);
-        assert_eq!(NodeKind::from_str("swarch"), NodeKind::Swarch);
-        assert_eq!(NodeKind::from_str("req"), NodeKind::Req);
+impl From<&str> for NodeKind {
+    fn from(s: &str) -> Self {
+        Self::new(s)
     }
+}
 
-    #[test]
-    fn test_from_str_case_insensitive() {
-        assert_eq!(NodeKind::from_str("PERSON"), NodeKind::Person);
-        assert_eq!(NodeKind::from_str("TeAm"), NodeKind::Team);
-        assert_eq!(NodeKind::from_str(" test "), NodeKind::Test);
+impl From<String> for NodeKind {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeKind {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        String::deserialize(d).map(Self::new)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn test_from_str_unknown() {
-        assert_eq!(NodeKind::from_str("invalid"), NodeKind::Unknown);
-        assert_eq!(NodeKind::from_str(""), NodeKind::Unknown);
+    fn test_from_str_normalizes() {
+        assert_eq!(NodeKind::from_str("PERSON"), NodeKind::from("person"));
+        assert_eq!(NodeKind::from_str(" test ").as_str(), "test");
     }
 
     #[test]
-    fn test_as_str() {
-        assert_eq!(NodeKind::Person.as_str(), "person");
-        assert_eq!(NodeKind::Team.as_str(), "team");
-        assert_eq!(NodeKind::Unknown.as_str(), "unknown");
+    fn test_custom_types_are_kept() {
+        assert_eq!(NodeKind::from_str("hazard").as_str(), "hazard");
+        assert!(NodeKind::from_str("").is_empty());
     }
 
     #[test]
-    fn test_display() {
-        assert_eq!(format!("{}", NodeKind::Person), "person");
-        assert_eq!(format!("{}", NodeKind::Team), "team");
-        assert_eq!(format!("{}", NodeKind::Unknown), "unknown");
+    fn test_deserialize_and_display() {
+        let kinds: Vec<NodeKind> = serde_json::from_str(r#"["Req", "swreq"]"#).unwrap();
+        assert_eq!(kinds, vec![NodeKind::from("req"), NodeKind::from("swreq")]);
+        assert_eq!(format!("{}", kinds[0]), "req");
     }
 }
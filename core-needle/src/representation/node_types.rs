@@ -0,0 +1,145 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::representation::NodeKind;
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+// Declared need type, e.g `"swreq": { "is_a": "req", "aliases": ["sw_req"] }`.
+// Aliases are other spellings used by the export, they're folded into the type.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TypeDef {
+    #[serde(default)]
+    pub is_a: Option<NodeKind>,
+    #[serde(default)]
+    pub aliases: Vec<NodeKind>,
+}
+
+// Need types known to the graph - the ones used by the export plus the ones
+// declared in the config - with their aliases and the "is a" hierarchy.
+#[derive(Debug, Clone, Default)]
+pub struct NodeTypes {
+    known: BTreeSet<NodeKind>,
+    parents: HashMap<NodeKind, NodeKind>,
+    aliases: HashMap<NodeKind, NodeKind>,
+}
+
+impl NodeTypes {
+    pub fn new(defs: &BTreeMap<NodeKind, TypeDef>) -> Self {
+        let mut out = Self::default();
+        for (kind, def) in defs {
+            out.known.insert(kind.clone());
+            for alias in &def.aliases {
+                out.aliases.insert(alias.clone(), kind.clone());
+            }
+        }
+        // Parents are resolved once every alias is known
+        for (kind, def) in defs {
+            if let Some(parent) = &def.is_a {
+                let parent = out.resolve(parent.as_str());
+                out.known.insert(parent.clone());
+                out.parents.insert(kind.clone(), parent);
+            }
+        }
+        out
+    }
+
+    // Canonical type of a raw `type` value
+    pub fn resolve(&self, raw: &str) -> NodeKind {
+        let kind = NodeKind::new(raw);
+        self.aliases.get(&kind).cloned().unwrap_or(kind)
+    }
+
+    pub fn insert(&mut self, kind: NodeKind) {
+        self.known.insert(kind);
+    }
+
+    pub fn is_known(&self, kind: &NodeKind) -> bool {
+        self.known.contains(kind) || self.aliases.contains_key(kind)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.known.is_empty()
+    }
+
+    pub fn kinds(&self) -> impl Iterator<Item = &NodeKind> {
+        self.known.iter()
+    }
+
+    pub fn parent(&self, kind: &NodeKind) -> Option<&NodeKind> {
+        self.parents.get(kind)
+    }
+
+    // `kind` followed by its ancestors, nearest first
+    pub fn lineage(&self, kind: &NodeKind) -> Vec<NodeKind> {
+        let mut out = vec![self.resolve(kind.as_str())];
+        while let Some(parent) = out.last().and_then(|k| self.parent(k)) {
+            // A cycle in the config would loop forever otherwise
+            if out.contains(parent) {
+                break;
+            }
+            out.push(parent.clone());
+        }
+        out
+    }
+
+    // Whether `kind` is `ancestor` or one of its (transitive) subtypes
+    pub fn is_a(&self, kind: &NodeKind, ancestor: &NodeKind) -> bool {
+        let ancestor = self.resolve(ancestor.as_str());
+        self.lineage(kind).contains(&ancestor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types() -> NodeTypes {
+        let defs: BTreeMap<NodeKind, TypeDef> = serde_json::from_str(
+            r#"{
+                "swreq": { "is_a": "requirement", "aliases": ["sw_req"] },
+                "req": { "aliases": ["requirement"] },
+                "hwreq": { "is_a": "req" }
+            }"#,
+        )
+        .unwrap();
+        NodeTypes::new(&defs)
+    }
+
+    #[test]
+    fn aliases_resolve_to_the_declared_type() {
+        let t = types();
+        assert_eq!(t.resolve("SW_REQ"), NodeKind::from("swreq"));
+        assert_eq!(t.resolve("impl"), NodeKind::from("impl"));
+        assert!(t.is_known(&NodeKind::from("requirement")));
+        assert!(!t.is_known(&NodeKind::from("impl")));
+    }
+
+    #[test]
+    fn hierarchy_is_transitive_and_alias_aware() {
+        let t = types();
+        let swreq = NodeKind::from("swreq");
+        assert!(t.is_a(&swreq, &NodeKind::from("req")));
+        assert!(t.is_a(&NodeKind::from("sw_req"), &NodeKind::from("requirement")));
+        assert!(t.is_a(&swreq, &swreq));
+        assert!(!t.is_a(&NodeKind::from("req"), &swreq));
+        assert!(!t.is_a(&swreq, &NodeKind::from("hwreq")));
+        assert_eq!(
+            t.lineage(&swreq),
+            vec![NodeKind::from("swreq"), NodeKind::from("req")]
+        );
+    }
+
+    #[test]
+    fn cyclic_hierarchy_terminates() {
+        let defs: BTreeMap<NodeKind, TypeDef> =
+            serde_json::from_str(r#"{ "a": { "is_a": "b" }, "b": { "is_a": "a" } }"#).unwrap();
+        let t = NodeTypes::new(&defs);
+        assert!(t.is_a(&NodeKind::from("a"), &NodeKind::from("b")));
+        assert!(!t.is_a(&NodeKind::from("a"), &NodeKind::from("c")));
+    }
+}
@@ -0,0 +1,197 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::representation::{Graph, Issue, IssueCode, LinkType, Node, NodeId};
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const REVIEW_STATE_VERSION: u32 = 1;
+
+// Need options hashed into the fingerprint unless the state says otherwise
+pub const DEFAULT_FINGERPRINT_FIELDS: &[&str] = &["title", "content"];
+
+// What every downstream need was reviewed against. A need links to its
+// upstream needs (e.g a spec to the requirement it refines); when it is marked
+// reviewed the fingerprint of each of them is recorded. Links whose upstream
+// fingerprint differs on a later run are suspect until reviewed again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewState {
+    pub version: u32,
+    // Need options hashed into the fingerprint
+    pub fields: Vec<String>,
+    // Link types followed to the upstream needs, empty means any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_types: Vec<LinkType>,
+    // Downstream need -> upstream need -> upstream fingerprint at review time
+    #[serde(default)]
+    pub reviewed: BTreeMap<NodeId, BTreeMap<NodeId, String>>,
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_FINGERPRINT_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            Vec::new(),
+        )
+    }
+}
+
+impl ReviewState {
+    pub fn new(fields: Vec<String>, link_types: Vec<LinkType>) -> Self {
+        Self {
+            version: REVIEW_STATE_VERSION,
+            fields,
+            link_types,
+            reviewed: BTreeMap::new(),
+        }
+    }
+
+    // Stable across runs and platforms (FNV-1a), the file is kept in the repository
+    pub fn fingerprint(&self, node: &Node) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for field in &self.fields {
+            let value = node
+                .field_value(field)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            for byte in field.bytes().chain([0]).chain(value.bytes()).chain([0]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        format!("{hash:016x}")
+    }
+
+    fn upstream<'a>(&self, g: &'a Graph, id: &NodeId) -> BTreeSet<&'a NodeId> {
+        g.out_via(id, &self.link_types)
+            .into_iter()
+            .filter(|up| g.nodes.contains_key(*up))
+            .collect()
+    }
+
+    // Marks the need reviewed against the current state of its upstream needs,
+    // returns the number of links recorded
+    pub fn review(&mut self, g: &Graph, id: &NodeId) -> usize {
+        let recorded: BTreeMap<NodeId, String> = self
+            .upstream(g, id)
+            .into_iter()
+            .map(|up| (up.clone(), self.fingerprint(&g.nodes[up])))
+            .collect();
+        let count = recorded.len();
+        if recorded.is_empty() {
+            self.reviewed.remove(id);
+        } else {
+            self.reviewed.insert(id.clone(), recorded);
+        }
+        count
+    }
+
+    // One issue per link whose upstream need changed since the review. Links
+    // which were never reviewed are not suspect.
+    pub fn suspects(&self, g: &Graph) -> Vec<Issue> {
+        let mut out = Vec::new();
+        for (id, recorded) in &self.reviewed {
+            if !g.nodes.contains_key(id) {
+                continue;
+            }
+            for up in self.upstream(g, id) {
+                let Some(reviewed) = recorded.get(up) else {
+                    continue;
+                };
+                if *reviewed != self.fingerprint(&g.nodes[up]) {
+                    out.push(
+                        Issue::warn(
+                            IssueCode::SuspectLink,
+                            id.clone(),
+                            format!("{up} changed since {id} was last reviewed"),
+                        )
+                        .with_related(vec![up.clone()]),
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+    use crate::representation::Dataset;
+
+    fn graph(ds: &Dataset, version: &str) -> Graph {
+        Graph::new(ds.access_version(version).unwrap()).unwrap()
+    }
+
+    const NEEDS: &str = r#"{
+      "current_version": "2",
+      "versions": {
+        "1": { "needs": {
+          "REQ_1": { "id":"REQ_1","type":"req","title":"Brake","content":"within 2s","links":[] },
+          "REQ_2": { "id":"REQ_2","type":"req","title":"Steer","links":[] },
+          "SPEC_1": { "id":"SPEC_1","type":"spec","links":["REQ_1","REQ_2"] }
+        } },
+        "2": { "needs": {
+          "REQ_1": { "id":"REQ_1","type":"req","title":"Brake","content":"within 1s","links":[],"lineno":7 },
+          "REQ_2": { "id":"REQ_2","type":"req","title":"Steer","links":[],"lineno":9 },
+          "SPEC_1": { "id":"SPEC_1","type":"spec","links":["REQ_1","REQ_2"] }
+        } }
+      }
+    }"#;
+
+    #[test]
+    fn changed_upstream_makes_link_suspect_until_reviewed() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let (before, after) = (graph(&ds, "1"), graph(&ds, "2"));
+        let spec = NodeId::from("SPEC_1");
+
+        let mut state = ReviewState::default();
+        assert!(state.suspects(&after).is_empty());
+        assert_eq!(state.review(&before, &spec), 2);
+        assert!(state.suspects(&before).is_empty());
+
+        // Only the content of REQ_1 changed, `lineno` is not fingerprinted
+        let suspects = state.suspects(&after);
+        assert_eq!(suspects.len(), 1);
+        assert_eq!(suspects[0].code, IssueCode::SuspectLink);
+        assert_eq!(suspects[0].subject, spec);
+        assert_eq!(suspects[0].related, vec![NodeId::from("REQ_1")]);
+        assert_eq!(
+            suspects[0].detail,
+            "REQ_1 changed since SPEC_1 was last reviewed"
+        );
+
+        state.review(&after, &spec);
+        assert!(state.suspects(&after).is_empty());
+
+        let raw = serde_json::to_string(&state).unwrap();
+        let back: ReviewState = serde_json::from_str(&raw).unwrap();
+        assert_eq!(back, state);
+    }
+
+    #[test]
+    fn fingerprint_depends_on_selected_fields() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let (before, after) = (graph(&ds, "1"), graph(&ds, "2"));
+        let req = NodeId::from("REQ_1");
+
+        let default = ReviewState::default();
+        assert_ne!(
+            default.fingerprint(&before.nodes[&req]),
+            default.fingerprint(&after.nodes[&req])
+        );
+        let titles = ReviewState::new(vec!["title".into()], Vec::new());
+        assert_eq!(
+            titles.fingerprint(&before.nodes[&req]),
+            titles.fingerprint(&after.nodes[&req])
+        );
+    }
+}
@@ -0,0 +1,180 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::error::{Error, Result};
+use crate::representation::{Direction, Graph, LinkType, NodeId, NodeKind};
+use std::collections::BTreeSet;
+
+// Which part of the graph to extract. Without roots every need is taken,
+// otherwise the roots and the needs reachable from them within `depth`.
+// `kinds` and `link_types` (empty means any) restrict both the needs walked
+// through and the ones listed - the roots are always listed.
+#[derive(Debug, Default, Clone)]
+pub struct SubgraphOptions {
+    pub roots: Vec<NodeId>,
+    pub depth: Option<usize>,
+    pub kinds: Vec<NodeKind>,
+    pub link_types: Vec<LinkType>,
+    pub direction: Direction,
+}
+
+#[derive(Debug)]
+pub struct Subgraph<'a> {
+    // Sorted by id
+    pub nodes: Vec<&'a NodeId>,
+    // Links between the listed needs, always in their own direction
+    pub edges: Vec<SubgraphEdge<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SubgraphEdge<'a> {
+    pub from: &'a NodeId,
+    pub to: &'a NodeId,
+    pub link_type: &'a LinkType,
+}
+
+impl<'a> Subgraph<'a> {
+    pub fn extract(g: &'a Graph, options: &SubgraphOptions) -> Result<Self> {
+        let wanted =
+            |id: &NodeId| options.kinds.is_empty() || options.kinds.iter().any(|k| g.is_a(id, k));
+
+        let mut nodes: BTreeSet<&NodeId> = BTreeSet::new();
+        if options.roots.is_empty() {
+            nodes.extend(g.nodes.keys().filter(|id| wanted(id)));
+        } else {
+            let mut frontier = Vec::with_capacity(options.roots.len());
+            for root in &options.roots {
+                let (id, _) = g
+                    .nodes
+                    .get_key_value(root)
+                    .ok_or_else(|| Error::UnknownNeed { id: root.clone() })?;
+                if nodes.insert(id) {
+                    frontier.push(id);
+                }
+            }
+
+            let mut depth = 0;
+            while !frontier.is_empty() && options.depth.is_none_or(|max| depth < max) {
+                depth += 1;
+                let mut next = Vec::new();
+                for id in frontier {
+                    for nxt in g.via(id, &options.link_types, options.direction) {
+                        if g.nodes.contains_key(nxt) && wanted(nxt) && nodes.insert(nxt) {
+                            next.push(nxt);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+        }
+
+        let mut edges: Vec<SubgraphEdge<'a>> = nodes
+            .iter()
+            .flat_map(|from| {
+                g.out_edges(from).iter().map(move |e| SubgraphEdge {
+                    from,
+                    to: &e.target,
+                    link_type: &e.link_type,
+                })
+            })
+            .filter(|e| nodes.contains(e.to))
+            .filter(|e| options.link_types.is_empty() || options.link_types.contains(e.link_type))
+            .collect();
+        edges.sort();
+
+        Ok(Self {
+            nodes: nodes.into_iter().collect(),
+            edges,
+        })
+    }
+
+    // Types of the listed needs, sorted
+    pub fn kinds(&self, g: &Graph) -> Vec<NodeKind> {
+        let kinds: BTreeSet<NodeKind> = self.nodes.iter().filter_map(|id| g.kind_of(id)).collect();
+        kinds.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+
+    const NEEDS: &str = r#"{ "current_version": "1.0", "versions": { "1.0": { "needs": {
+        "REQ_1": { "id":"REQ_1","type":"req","links":["SPEC_1"] },
+        "SPEC_1": { "id":"SPEC_1","type":"spec","links":["TEST_1","IMPL_1"] },
+        "IMPL_1": { "id":"IMPL_1","type":"impl","links":["TEST_2"] },
+        "TEST_1": { "id":"TEST_1","type":"test","links":[] },
+        "TEST_2": { "id":"TEST_2","type":"test","links":[] },
+        "REQ_2": { "id":"REQ_2","type":"req","links":[] }
+    } } } }"#;
+
+    fn ids(sub: &Subgraph<'_>) -> Vec<String> {
+        sub.nodes.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn roots_depth_and_kinds() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+
+        let all = Subgraph::extract(&g, &SubgraphOptions::default()).unwrap();
+        assert_eq!(all.nodes.len(), 6);
+        assert_eq!(all.edges.len(), 4);
+
+        let near = Subgraph::extract(
+            &g,
+            &SubgraphOptions {
+                roots: vec![NodeId::from("REQ_1")],
+                depth: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(ids(&near), vec!["IMPL_1", "REQ_1", "SPEC_1", "TEST_1"]);
+        assert_eq!(near.edges.len(), 3);
+
+        // `impl` is not walked through, so TEST_2 is out of reach
+        let no_impl = Subgraph::extract(
+            &g,
+            &SubgraphOptions {
+                roots: vec![NodeId::from("REQ_1")],
+                kinds: vec![NodeKind::from("spec"), NodeKind::from("test")],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(ids(&no_impl), vec!["REQ_1", "SPEC_1", "TEST_1"]);
+        assert_eq!(
+            no_impl.kinds(&g),
+            vec![
+                NodeKind::from("req"),
+                NodeKind::from("spec"),
+                NodeKind::from("test")
+            ]
+        );
+
+        let up = Subgraph::extract(
+            &g,
+            &SubgraphOptions {
+                roots: vec![NodeId::from("TEST_2")],
+                direction: Direction::Backward,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(ids(&up), vec!["IMPL_1", "REQ_1", "SPEC_1", "TEST_2"]);
+
+        let missing = SubgraphOptions {
+            roots: vec![NodeId::from("NOPE")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            Subgraph::extract(&g, &missing),
+            Err(Error::UnknownNeed { .. })
+        ));
+    }
+}
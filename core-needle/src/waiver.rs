@@ -0,0 +1,346 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use crate::date::Date;
+use crate::pattern::glob_match;
+use crate::policy::{RuleReport, retain_issues};
+use crate::representation::{Graph, Issue, IssueCode, NodeId};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Need option holding waivers of the need itself, either one waiver object or
+// a list of them. `need` may be left out there.
+pub const WAIVER_FIELD: &str = "needle_waiver";
+
+pub const WAIVERS_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaiverFile {
+    pub version: u32,
+    #[serde(default)]
+    pub waivers: Vec<Waiver>,
+}
+
+// Documented deviation: issues with `code` on needs matching `need` are
+// accepted until `expires` (inclusive). `need` is a need id or a glob pattern
+// such as `IMPL_LEGACY_*`, `code` is the rule code or the issue code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Waiver {
+    pub code: String,
+    #[serde(default)]
+    pub need: String,
+    #[serde(default)]
+    pub justification: String,
+    #[serde(default)]
+    pub expires: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaiverOrigin {
+    File,
+    Need(NodeId),
+}
+
+// Every suppressed issue together with the waiver which accepted it
+#[derive(Debug, Serialize)]
+pub struct AppliedWaiver {
+    pub code: String,
+    pub need: String,
+    pub justification: String,
+    pub expires: Date,
+    pub origin: WaiverOrigin,
+    pub issue: Issue,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct WaiverOutcome {
+    pub applied: Vec<AppliedWaiver>,
+}
+
+struct Entry {
+    waiver: Waiver,
+    origin: WaiverOrigin,
+    // None when the waiver is invalid, it's reported and never applied
+    expires: Option<Date>,
+    used: bool,
+}
+
+impl Entry {
+    fn matches(&self, codes: &[&str], subject: &NodeId) -> bool {
+        codes
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(&self.waiver.code))
+            && glob_match(&self.waiver.need, subject.as_ref())
+    }
+
+    fn subject(&self) -> NodeId {
+        match &self.origin {
+            WaiverOrigin::Need(id) => id.clone(),
+            WaiverOrigin::File => NodeId::from(self.waiver.need.as_str()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Waivers {
+    entries: Vec<Entry>,
+    // Problems found while collecting, reported by `apply`
+    problems: Vec<Issue>,
+}
+
+impl Waivers {
+    pub fn new(file: Option<WaiverFile>) -> Self {
+        let mut out = Self::default();
+        for waiver in file.map(|f| f.waivers).unwrap_or_default() {
+            out.push(waiver, WaiverOrigin::File);
+        }
+        out
+    }
+
+    // Adds the waivers declared on the needs through `needle_waiver`
+    pub fn with_graph(mut self, g: &Graph) -> Self {
+        let mut ids: Vec<&NodeId> = g.nodes.keys().collect();
+        ids.sort();
+
+        for id in ids {
+            let Some(raw) = g.nodes[id].extra.get(WAIVER_FIELD) else {
+                continue;
+            };
+            let items = match raw {
+                Value::Array(items) => items.clone(),
+                other => vec![other.clone()],
+            };
+            for item in items {
+                match serde_json::from_value::<Waiver>(item) {
+                    Ok(mut waiver) => {
+                        if waiver.need.is_empty() {
+                            waiver.need = id.to_string();
+                        }
+                        self.push(waiver, WaiverOrigin::Need(id.clone()));
+                    }
+                    Err(e) => self.problems.push(Issue::error(
+                        IssueCode::WaiverInvalid,
+                        id.clone(),
+                        format!("`{WAIVER_FIELD}` can't be read: {e}"),
+                    )),
+                }
+            }
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn push(&mut self, waiver: Waiver, origin: WaiverOrigin) {
+        let mut entry = Entry {
+            expires: Date::parse(&waiver.expires),
+            waiver,
+            origin,
+            used: false,
+        };
+
+        let mut missing = Vec::new();
+        if entry.waiver.code.trim().is_empty() {
+            missing.push("a code");
+        }
+        if entry.waiver.need.trim().is_empty() {
+            missing.push("a need id or pattern");
+        }
+        if entry.waiver.justification.trim().is_empty() {
+            missing.push("a justification");
+        }
+        if entry.expires.is_none() {
+            missing.push("an expiry date (YYYY-MM-DD)");
+        }
+
+        if !missing.is_empty() {
+            self.problems.push(Issue::error(
+                IssueCode::WaiverInvalid,
+                entry.subject(),
+                format!(
+                    "waiver for `{}` is missing {}",
+                    entry.waiver.code,
+                    missing.join(", ")
+                ),
+            ));
+            entry.expires = None;
+        }
+        self.entries.push(entry);
+    }
+
+    // Drops every issue accepted by a valid waiver which has not expired by
+    // `today`. Invalid, expired and unused waivers are appended to
+    // `graph_issues` so they go through the same reporting and gating.
+    pub fn apply(
+        mut self,
+        graph_issues: &mut Vec<Issue>,
+        reports: &mut [RuleReport<'_>],
+        today: Date,
+    ) -> WaiverOutcome {
+        let mut applied = Vec::new();
+
+        // Rule code or issue code, for graph issues both are the same
+        retain_issues(graph_issues, reports, |code, issue| {
+            let codes = [code, issue.code.to_str()];
+            let hit = self.entries.iter_mut().find(|e| {
+                e.expires.is_some_and(|exp| today <= exp) && e.matches(&codes, &issue.subject)
+            });
+            let Some(entry) = hit else {
+                return true;
+            };
+            entry.used = true;
+            applied.push(AppliedWaiver {
+                code: entry.waiver.code.clone(),
+                need: entry.waiver.need.clone(),
+                justification: entry.waiver.justification.clone(),
+                expires: entry.expires.expect("only valid waivers are applied"),
+                origin: entry.origin.clone(),
+                issue: issue.clone(),
+            });
+            false
+        });
+
+        graph_issues.append(&mut self.problems);
+        for entry in &self.entries {
+            let Some(expires) = entry.expires else {
+                continue;
+            };
+            if today > expires {
+                graph_issues.push(Issue::warn(
+                    IssueCode::WaiverExpired,
+                    entry.subject(),
+                    format!(
+                        "waiver for `{}` on `{}` expired on {expires}",
+                        entry.waiver.code, entry.waiver.need
+                    ),
+                ));
+            } else if !entry.used {
+                graph_issues.push(Issue::warn(
+                    IssueCode::WaiverUnused,
+                    entry.subject(),
+                    format!(
+                        "waiver for `{}` on `{}` matches no issue",
+                        entry.waiver.code, entry.waiver.need
+                    ),
+                ));
+            }
+        }
+
+        WaiverOutcome { applied }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::populate_from_str;
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "IMPL_1": { "id":"IMPL_1","type":"impl","links":["REQ_1","REQ_1"],
+                        "needle_waiver": { "code":"duplicate_link",
+                                           "justification":"generated from the legacy export",
+                                           "expires":"2030-01-01" } },
+            "IMPL_2": { "id":"IMPL_2","type":"impl","links":["REQ_1","REQ_1"],
+                        "needle_waiver": [{ "code":"duplicate_link", "expires":"2030-01-01" }] }
+          }
+        }
+      }
+    }"#;
+
+    fn graph() -> Graph {
+        let ds = populate_from_str(NEEDS).unwrap();
+        Graph::new(ds.access_current_version().unwrap()).unwrap()
+    }
+
+    fn codes(issues: &[Issue]) -> Vec<IssueCode> {
+        issues.iter().map(|i| i.code.clone()).collect()
+    }
+
+    #[test]
+    fn need_waivers_are_applied_or_reported_invalid() {
+        let mut g = graph();
+        let waivers = Waivers::new(None).with_graph(&g);
+        assert_eq!(waivers.len(), 2);
+
+        let outcome = waivers.apply(&mut g.issues, &mut [], Date::parse("2025-06-01").unwrap());
+
+        assert_eq!(outcome.applied.len(), 1);
+        assert_eq!(outcome.applied[0].issue.subject, NodeId::from("IMPL_1"));
+        assert_eq!(
+            outcome.applied[0].origin,
+            WaiverOrigin::Need(NodeId::from("IMPL_1"))
+        );
+        assert!(
+            !g.issues
+                .iter()
+                .any(|i| i.code == IssueCode::DuplicateLink && i.subject == NodeId::from("IMPL_1"))
+        );
+        assert!(
+            g.issues
+                .iter()
+                .any(|i| i.code == IssueCode::DuplicateLink && i.subject == NodeId::from("IMPL_2"))
+        );
+        let invalid = g.issues.last().unwrap();
+        assert_eq!(invalid.code, IssueCode::WaiverInvalid);
+        assert!(invalid.detail.contains("justification"));
+    }
+
+    #[test]
+    fn file_waivers_match_patterns_and_expire() {
+        let file: WaiverFile = serde_json::from_str(
+            r#"{ "version": 1, "waivers": [
+                { "code":"BROKEN_LINK", "need":"IMPL_*", "justification":"migration", "expires":"2025-01-31" },
+                { "code":"self_loop", "need":"REQ_1", "justification":"legacy", "expires":"2030-01-01" }
+            ] }"#,
+        )
+        .unwrap();
+
+        let mut issues = vec![
+            Issue::error(IssueCode::BrokenLink, NodeId::from("IMPL_1"), "a"),
+            Issue::error(IssueCode::BrokenLink, NodeId::from("IMPL_2"), "b"),
+        ];
+        let outcome = Waivers::new(Some(file.clone())).apply(
+            &mut issues,
+            &mut [],
+            Date::parse("2025-01-31").unwrap(),
+        );
+        assert_eq!(outcome.applied.len(), 2);
+        assert_eq!(codes(&issues), vec![IssueCode::WaiverUnused]);
+
+        let mut issues = vec![Issue::error(
+            IssueCode::BrokenLink,
+            NodeId::from("IMPL_1"),
+            "a",
+        )];
+        let outcome = Waivers::new(Some(file)).apply(
+            &mut issues,
+            &mut [],
+            Date::parse("2025-02-01").unwrap(),
+        );
+        assert!(outcome.applied.is_empty());
+        assert_eq!(
+            codes(&issues),
+            vec![
+                IssueCode::BrokenLink,
+                IssueCode::WaiverExpired,
+                IssueCode::WaiverUnused
+            ]
+        );
+    }
+}
@@ -7,7 +7,14 @@
 use colored::*;
 use std::fmt;
 
+use core_needle::baseline::BaselineOutcome;
+use core_needle::change::{ChangeReport, ChangedIssue};
+use core_needle::diff::Diff;
+use core_needle::impact::Impact;
+use core_needle::policy::{Diagnostic, RuleReport};
 use core_needle::representation::{Graph, Issue, Severity};
+use core_needle::waiver::{WaiverOrigin, WaiverOutcome};
+use core_needle::Error;
 
 // This is just a helper util for printing the outputs to the terminal.
 // Without formatting the output with colors it's hard to follow the output
@@ -22,7 +29,7 @@ pub enum ColorMode {
 }
 
 impl ColorMode {
-    fn paint<'a>(&self, sev: Severity, s: &'a str) -> String {
+    fn paint(&self, sev: Severity, s: &str) -> String {
         match (self, sev) {
             (ColorMode::Never, _) => s.to_string(),
             (_, Severity::Error) => s.red().bold().to_string(),
@@ -45,6 +52,38 @@ pub struct IssuesCli<'a> {
     issues: &'a [Issue],
     colors: ColorMode,
 }
+pub struct DiagnosticsCli<'a> {
+    diagnostics: &'a [Diagnostic],
+    severity: Severity,
+    colors: ColorMode,
+}
+pub struct BaselineCli<'a> {
+    outcome: &'a BaselineOutcome,
+    colors: ColorMode,
+}
+pub struct WaiversCli<'a> {
+    outcome: &'a WaiverOutcome,
+    colors: ColorMode,
+}
+pub struct ErrorCli<'a> {
+    error: &'a Error,
+    colors: ColorMode,
+}
+pub struct MetricsCli<'a, 'p> {
+    reports: &'a [RuleReport<'p>],
+    colors: ColorMode,
+}
+pub struct ChangesCli<'a> {
+    report: &'a ChangeReport,
+    colors: ColorMode,
+}
+pub struct ImpactCli<'a> {
+    impact: &'a Impact,
+}
+pub struct DiffCli<'a> {
+    diff: &'a Diff,
+    colors: ColorMode,
+}
 
 impl<'a> GraphNodesCli<'a> {
     pub fn new(graph: &'a Graph) -> Self {
@@ -67,6 +106,51 @@ impl<'a> IssuesCli<'a> {
     }
 }
 
+impl<'a> DiagnosticsCli<'a> {
+    pub fn new(diagnostics: &'a [Diagnostic], severity: Severity, colors: ColorMode) -> Self {
+        Self {
+            diagnostics,
+            severity,
+            colors,
+        }
+    }
+}
+impl<'a> BaselineCli<'a> {
+    pub fn new(outcome: &'a BaselineOutcome, colors: ColorMode) -> Self {
+        Self { outcome, colors }
+    }
+}
+impl<'a> WaiversCli<'a> {
+    pub fn new(outcome: &'a WaiverOutcome, colors: ColorMode) -> Self {
+        Self { outcome, colors }
+    }
+}
+impl<'a> ErrorCli<'a> {
+    pub fn new(error: &'a Error, colors: ColorMode) -> Self {
+        Self { error, colors }
+    }
+}
+impl<'a> ChangesCli<'a> {
+    pub fn new(report: &'a ChangeReport, colors: ColorMode) -> Self {
+        Self { report, colors }
+    }
+}
+impl<'a> ImpactCli<'a> {
+    pub fn new(impact: &'a Impact) -> Self {
+        Self { impact }
+    }
+}
+impl<'a> DiffCli<'a> {
+    pub fn new(diff: &'a Diff, colors: ColorMode) -> Self {
+        Self { diff, colors }
+    }
+}
+impl<'a, 'p> MetricsCli<'a, 'p> {
+    pub fn new(reports: &'a [RuleReport<'p>], colors: ColorMode) -> Self {
+        Self { reports, colors }
+    }
+}
+
 impl fmt::Display for GraphNodesCli<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Nodes ({})", self.graph.nodes_len())?;
@@ -91,7 +175,7 @@ impl fmt::Display for GraphKindsCli<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Kinds ({})", self.graph.kinds.len())?;
         for (kind, ids) in &self.graph.kinds {
-            writeln!(f, "  {kind:?}: {:?}", ids)?;
+            writeln!(f, "  {kind}: {:?}", ids)?;
         }
         Ok(())
     }
@@ -114,6 +198,199 @@ impl fmt::Display for IssuesCli<'_> {
     }
 }
 
+impl fmt::Display for DiagnosticsCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Policy diagnostics ({})", self.diagnostics.len())?;
+        for diag in self.diagnostics {
+            let sev_tag = format!("[{}]", self.severity);
+            let sev_colored = self.colors.paint(self.severity, &sev_tag);
+            writeln!(f, "  {} [{}] {}", sev_colored, diag.kind, diag)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for BaselineCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Baseline: {} known issue(s) suppressed, {} fixed entries can be removed",
+            self.outcome.suppressed,
+            self.outcome.fixed.len()
+        )?;
+        for entry in &self.outcome.fixed {
+            let tag = self.colors.paint(Severity::Suggestion, "[fixed]");
+            writeln!(
+                f,
+                "  {} [{}] - {:?}, detail: {}",
+                tag, entry.code, entry.subject, entry.detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for WaiversCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Applied waivers ({})", self.outcome.applied.len())?;
+        for applied in &self.outcome.applied {
+            let tag = self.colors.paint(Severity::Suggestion, "[waived]");
+            let origin = match &applied.origin {
+                WaiverOrigin::File => "waiver file".to_string(),
+                WaiverOrigin::Need(id) => format!("need {id}"),
+            };
+            writeln!(
+                f,
+                "  {} [{}] - {:?}, detail: {}",
+                tag, applied.code, applied.issue.subject, applied.issue.detail
+            )?;
+            writeln!(
+                f,
+                "      justification: {} (expires {}, from {})",
+                applied.justification, applied.expires, origin
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MetricsCli<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let measured: Vec<_> = self
+            .reports
+            .iter()
+            .filter_map(|r| r.metric.as_ref().map(|m| (r, m)))
+            .collect();
+        writeln!(f, "Metrics ({})", measured.len())?;
+        for (report, metric) in measured {
+            let tag = if metric.below_min() || metric.above_max() {
+                self.colors.paint(Severity::Error, "[failed]")
+            } else {
+                self.colors.paint(Severity::Suggestion, "[passed]")
+            };
+            writeln!(
+                f,
+                "  {} [{}] - {}",
+                tag,
+                report.rule.code_or_id(),
+                metric.describe()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ChangesCli<'_> {
+    fn section(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        title: &str,
+        items: &[ChangedIssue],
+    ) -> fmt::Result {
+        writeln!(f, "{} ({})", title, items.len())?;
+        for item in items {
+            let issue = &item.issue;
+            let sev_tag = format!("[{}]", issue.severity);
+            let sev_colored = self.colors.paint(issue.severity, &sev_tag);
+            writeln!(
+                f,
+                "  {} [{}] - {:?}, detail: {}",
+                sev_colored, item.rule_code, issue.subject, issue.detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ChangesCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.section(f, "Introduced issues", &self.report.introduced)?;
+        self.section(f, "Fixed issues", &self.report.fixed)?;
+        self.section(f, "Issues on changed needs", &self.report.touched)
+    }
+}
+
+impl fmt::Display for ImpactCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let changed: Vec<&str> = self.impact.changed.iter().map(|id| id.as_ref()).collect();
+        writeln!(
+            f,
+            "Impact of {} changed need(s): {}",
+            changed.len(),
+            changed.join(", ")
+        )?;
+        writeln!(f, "Affected needs ({})", self.impact.affected.len())?;
+        for (kind, by_distance) in self.impact.by_kind() {
+            let total: usize = by_distance.values().map(Vec::len).sum();
+            writeln!(f, "  {kind} ({total})")?;
+            for (distance, ids) in by_distance {
+                let ids: Vec<&str> = ids.iter().map(|id| id.as_ref()).collect();
+                writeln!(f, "    {distance}: {}", ids.join(", "))?;
+            }
+        }
+        let documents = self.impact.documents();
+        writeln!(f, "Affected documents ({})", documents.len())?;
+        for doc in documents {
+            writeln!(f, "  {doc}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DiffCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = self.diff;
+        writeln!(
+            f,
+            "Diff {} -> {}: {} added, {} removed, {} modified",
+            d.base_version,
+            d.head_version,
+            d.added.len(),
+            d.removed.len(),
+            d.modified.len()
+        )?;
+        for id in &d.added {
+            let tag = self.colors.paint(Severity::Suggestion, "[added]");
+            writeln!(f, "  {} {}", tag, id)?;
+        }
+        for id in &d.removed {
+            let tag = self.colors.paint(Severity::Error, "[removed]");
+            writeln!(f, "  {} {}", tag, id)?;
+        }
+        for need in &d.modified {
+            let tag = self.colors.paint(Severity::Warning, "[modified]");
+            writeln!(f, "  {} {}", tag, need.id)?;
+            for change in &need.fields {
+                let show = |v: &Option<serde_json::Value>| match v {
+                    Some(v) => v.to_string(),
+                    None => "-".to_string(),
+                };
+                writeln!(
+                    f,
+                    "      {}: {} -> {}",
+                    change.field,
+                    show(&change.base),
+                    show(&change.head)
+                )?;
+            }
+            for link in &need.links_added {
+                writeln!(f, "      + {} {}", link.link_type, link.target)?;
+            }
+            for link in &need.links_removed {
+                writeln!(f, "      - {} {}", link.link_type, link.target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ErrorCli<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tag = self.colors.paint(Severity::Error, "error:");
+        write!(f, "{} {}", tag, self.error)
+    }
+}
+
 pub fn nodes<'a>(g: &'a Graph) -> GraphNodesCli<'a> {
     GraphNodesCli::new(g)
 }
@@ -129,3 +406,31 @@ pub fn issues<'a>(g: &'a Graph, colors: ColorMode) -> IssuesCli<'a> {
 pub fn issues_from<'a>(items: &'a [Issue], colors: ColorMode) -> IssuesCli<'a> {
     IssuesCli::new(items, colors)
 }
+pub fn diagnostics(
+    items: &[Diagnostic],
+    severity: Severity,
+    colors: ColorMode,
+) -> DiagnosticsCli<'_> {
+    DiagnosticsCli::new(items, severity, colors)
+}
+pub fn baseline(outcome: &BaselineOutcome, colors: ColorMode) -> BaselineCli<'_> {
+    BaselineCli::new(outcome, colors)
+}
+pub fn waivers(outcome: &WaiverOutcome, colors: ColorMode) -> WaiversCli<'_> {
+    WaiversCli::new(outcome, colors)
+}
+pub fn metrics<'a, 'p>(reports: &'a [RuleReport<'p>], colors: ColorMode) -> MetricsCli<'a, 'p> {
+    MetricsCli::new(reports, colors)
+}
+pub fn changes(report: &ChangeReport, colors: ColorMode) -> ChangesCli<'_> {
+    ChangesCli::new(report, colors)
+}
+pub fn impact(impact: &Impact) -> ImpactCli<'_> {
+    ImpactCli::new(impact)
+}
+pub fn diff(diff: &Diff, colors: ColorMode) -> DiffCli<'_> {
+    DiffCli::new(diff, colors)
+}
+pub fn error(err: &Error, colors: ColorMode) -> ErrorCli<'_> {
+    ErrorCli::new(err, colors)
+}
@@ -0,0 +1,71 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use core_needle::policy::{coded_issues, RuleReport};
+use core_needle::representation::{Issue, Severity};
+
+// Decides which issues make the run fail. Codes are matched case-insensitively
+// against the issue code and, for policy issues, the code of the rule.
+// `deny` wins over `allow`, both win over `fail_on`.
+#[derive(Debug, Default)]
+pub struct Gate {
+    pub fail_on: Option<Severity>,
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl Gate {
+    pub fn fails(&self, issue: &Issue, rule_code: Option<&str>) -> bool {
+        let matches = |codes: &[String]| {
+            codes.iter().any(|c| {
+                c.eq_ignore_ascii_case(issue.code.to_str())
+                    || rule_code.is_some_and(|r| c.eq_ignore_ascii_case(r))
+            })
+        };
+
+        if matches(&self.deny) {
+            return true;
+        }
+        if matches(&self.allow) {
+            return false;
+        }
+        self.fail_on.is_some_and(|level| issue.severity >= level)
+    }
+
+    // Number of issues which fail the run
+    pub fn count(&self, graph_issues: &[Issue], reports: &[RuleReport<'_>]) -> usize {
+        // For graph issues the code is the issue code itself
+        coded_issues(graph_issues, reports)
+            .filter(|(code, issue)| self.fails(issue, Some(code)))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_needle::representation::{IssueCode, NodeId};
+
+    #[test]
+    fn fail_on_threshold_and_overrides() {
+        let warn = Issue::warn(IssueCode::DuplicateLink, NodeId::from("A"), "dup");
+        let err = Issue::error(IssueCode::BrokenLink, NodeId::from("B"), "broken");
+        let hint = Issue::suggest(IssueCode::DandlingNode, NodeId::from("C"), "lonely");
+
+        let gate = Gate::default();
+        assert!(!gate.fails(&err, None));
+
+        let gate = Gate {
+            fail_on: Some(Severity::Warning),
+            allow: vec!["duplicate_link".to_string()],
+            deny: vec!["REQ_NEEDS_TEST".to_string()],
+        };
+        assert!(gate.fails(&err, None));
+        assert!(!gate.fails(&warn, None));
+        assert!(!gate.fails(&hint, None));
+        assert!(gate.fails(&hint, Some("req_needs_test")));
+    }
+}
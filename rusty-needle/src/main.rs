@@ -5,24 +5,381 @@
 // -----------------------------------------------------------------------------
 
 pub mod cli_printers;
+pub mod gate;
+pub mod reports;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
-use core_needle::io::{load_graph_from_file, load_policy_from_file};
-use core_needle::policy::evaluator::evaluate;
+use core_needle::baseline::{Baseline, BaselineOutcome};
+use core_needle::change::ChangeReport;
+use core_needle::date::Date;
+use core_needle::diff::{Diff, DiffOptions};
+use core_needle::impact::{Impact, ImpactOptions};
+use core_needle::io::{
+    graph_for_policy, load_baseline_from_file, load_graph_for_policy, load_graph_from_file,
+    load_metamodel_from_file, load_policy_from_file, load_review_state_from_file,
+    load_waivers_from_file, populate_from_file, save_baseline_to_file, save_report_to_file,
+    save_review_state_to_file,
+};
+use core_needle::matrix::{Matrix, MatrixOptions};
+use core_needle::policy::evaluator::{evaluate_rules, RuleReport};
 use core_needle::policy::model::Policies;
+use core_needle::policy::{validate_for, Diagnostic};
 use core_needle::representation::graph::Graph;
-use core_needle::representation::Issue;
+use core_needle::representation::{
+    Dataset, Direction, Issue, LinkType, NodeId, NodeKind, Severity, VersionAccessor,
+};
+use core_needle::review::ReviewState;
+use core_needle::subgraph::{Subgraph, SubgraphOptions};
+use core_needle::waiver::{WaiverOutcome, Waivers};
 
 use crate::cli_printers::*;
+use crate::gate::Gate;
+use crate::reports::graph::{GraphExport, GraphFormat};
+use crate::reports::json::JsonReport;
+use crate::reports::junit::JunitReport;
+use crate::reports::matrix::{MatrixFormat, MatrixReport};
+use crate::reports::sarif::SarifLog;
+
+// Exit codes:
+//   0 - no issue matched the gate (`--fail-on`, `--deny`)
+//   1 - policy violations found which matched the gate
+//   2 - invalid command line, reported by clap
+//   3 - needs or policies could not be loaded, or the policy could not be evaluated
+const EXIT_VIOLATIONS: u8 = 1;
+const EXIT_LOAD_ERROR: u8 = 3;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum FailOn {
+    Error,
+    Warning,
+    Suggestion,
+}
+
+impl From<FailOn> for Severity {
+    fn from(f: FailOn) -> Self {
+        match f {
+            FailOn::Error => Severity::Error,
+            FailOn::Warning => Severity::Warning,
+            FailOn::Suggestion => Severity::Suggestion,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Colored human readable output
+    Text,
+    /// Single JSON document on stdout
+    Json,
+    /// SARIF 2.1.0 log on stdout
+    Sarif,
+    /// JUnit XML on stdout, one testsuite per rule and one testcase per selected need
+    Junit,
+}
 
 /// Validate Sphinx-Needs JSON with policy rules
 #[derive(Parser, Debug)]
 #[command(name = "rusty-needle", version)]
 #[command(about = "Validate Sphinx-Needs JSON with policy rules", long_about = None)]
-struct Args {
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    // Running without a subcommand checks the needs against the policy
+    #[command(flatten)]
+    inputs: Option<InputArgs>,
+
+    #[command(flatten)]
+    check: CheckArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage the baseline of known issues
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineCommand,
+    },
+    /// Write a traceability matrix from one need type to others
+    Matrix(MatrixArgs),
+    /// Export the graph or a part of it as a DOT, Mermaid or PlantUML diagram
+    Graph(GraphArgs),
+    /// Compare two versions of the needs, from one export or two
+    Diff(DiffArgs),
+    /// Report only the issues a change introduces, fixes or leaves on the needs it touches
+    Changes(ChangesArgs),
+    /// Mark needs reviewed against the current state of their upstream needs
+    Review(ReviewArgs),
+    /// List the needs affected by changed needs, grouped by type and distance
+    Impact(ImpactArgs),
+}
+
+#[derive(Subcommand, Debug)]
+enum BaselineCommand {
+    /// Record every current issue into a baseline file
+    Write {
+        #[command(flatten)]
+        inputs: InputArgs,
+
+        /// Path of the baseline file to write
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Review state file, suspect links are recorded like any other issue
+        #[arg(long)]
+        review_state: Option<PathBuf>,
+
+        /// Waiver file, issues it accepts are not recorded
+        #[arg(long)]
+        waivers: Option<PathBuf>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct MatrixArgs {
+    /// Path to needs file (Sphinx-Needs JSON)
+    #[arg(long)]
+    needs: PathBuf,
+
+    /// Policies file declaring the need and link types of the export
+    #[arg(long)]
+    policies: Option<PathBuf>,
+
+    /// Need type of the rows, e.g `req`
+    #[arg(long)]
+    source: NodeKind,
+
+    /// Need types of the columns, e.g `test,testrun`
+    #[arg(long, value_delimiter = ',', required = true)]
+    targets: Vec<NodeKind>,
+
+    /// Longest path followed, 1 lists direct links only, 0 follows any length
+    #[arg(long, default_value_t = 1)]
+    max_hops: usize,
+
+    /// Link direction followed from the source needs
+    #[arg(long, value_enum, default_value_t = LinkDirection::Forward)]
+    direction: LinkDirection,
+
+    /// Link types followed, all of them when not given
+    #[arg(long, value_delimiter = ',')]
+    link_types: Vec<LinkType>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = MatrixFormat::Csv)]
+    format: MatrixFormat,
+
+    /// File to write the matrix to, stdout when not given
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct GraphArgs {
+    /// Path to needs file (Sphinx-Needs JSON)
+    #[arg(long)]
+    needs: PathBuf,
+
+    /// Policies file, needs with issues are highlighted by their worst severity
+    #[arg(long)]
+    policies: Option<PathBuf>,
+
+    /// Needs the diagram starts from, e.g `REQ_1,REQ_2`, the whole graph when not given
+    #[arg(long = "root", value_delimiter = ',', value_name = "ID")]
+    roots: Vec<NodeId>,
+
+    /// Number of links followed from the roots, unlimited when not given
+    #[arg(long)]
+    depth: Option<usize>,
+
+    /// Need types shown and walked through, all of them when not given
+    #[arg(long, value_delimiter = ',')]
+    kinds: Vec<NodeKind>,
+
+    /// Link types shown and followed, all of them when not given
+    #[arg(long, value_delimiter = ',')]
+    link_types: Vec<LinkType>,
+
+    /// Link direction followed from the roots
+    #[arg(long, value_enum, default_value_t = LinkDirection::Forward)]
+    direction: LinkDirection,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    format: GraphFormat,
+
+    /// File to write the diagram to, stdout when not given
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct DiffArgs {
+    /// Needs file of the base, e.g the export of the main branch
+    #[arg(long)]
+    base: PathBuf,
+
+    /// Needs file of the head, the base file when not given
+    #[arg(long)]
+    head: Option<PathBuf>,
+
+    /// Version of the base file compared, its `current_version` when not given
+    #[arg(long)]
+    base_version: Option<String>,
+
+    /// Version of the head file compared, its `current_version` when not given
+    #[arg(long)]
+    head_version: Option<String>,
+
+    /// Need option never reported as changed, on top of the export bookkeeping ones (repeatable)
+    #[arg(long = "ignore-field", value_name = "FIELD")]
+    ignore_fields: Vec<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+    format: DiffFormat,
+}
+
+#[derive(Args, Debug)]
+struct ChangesArgs {
+    // `--needs` is the head, e.g the export of the merge request
+    #[command(flatten)]
+    inputs: InputArgs,
+
+    /// Needs file of the base, e.g the export of the target branch
+    #[arg(long)]
+    base: PathBuf,
+
+    /// Version of the base file compared, its `current_version` when not given
+    #[arg(long)]
+    base_version: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+    format: DiffFormat,
+
+    /// Exit with 1 when any introduced issue of this severity or higher is found
+    #[arg(long, value_enum)]
+    fail_on: Option<FailOn>,
+
+    /// Issue or rule code which never fails the run (repeatable)
+    #[arg(long = "allow", value_name = "CODE")]
+    allow: Vec<String>,
+
+    /// Issue or rule code which always fails the run (repeatable)
+    #[arg(long = "deny", value_name = "CODE")]
+    deny: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct ReviewArgs {
+    /// Path to needs file (Sphinx-Needs JSON)
+    #[arg(long)]
+    needs: PathBuf,
+
+    /// Policies file declaring the link types of the export
+    #[arg(long)]
+    policies: Option<PathBuf>,
+
+    /// Review state file, created when missing
+    #[arg(long)]
+    state: PathBuf,
+
+    /// Needs marked reviewed, every need when none is given
+    #[arg(value_name = "ID", value_delimiter = ',')]
+    ids: Vec<NodeId>,
+
+    /// Need options fingerprinted, `title,content` when not given (new state files only)
+    #[arg(long, value_delimiter = ',')]
+    fields: Vec<String>,
+
+    /// Link types followed to the upstream needs, all of them when not given (new state files only)
+    #[arg(long, value_delimiter = ',')]
+    link_types: Vec<LinkType>,
+}
+
+#[derive(Args, Debug)]
+struct ImpactArgs {
+    /// Path to needs file (Sphinx-Needs JSON)
+    #[arg(long)]
+    needs: PathBuf,
+
+    /// Policies file declaring the need and link types of the export
+    #[arg(long)]
+    policies: Option<PathBuf>,
+
+    /// Changed needs, e.g `REQ_1,REQ_2`
+    #[arg(
+        value_name = "ID",
+        value_delimiter = ',',
+        required_unless_present = "base"
+    )]
+    ids: Vec<NodeId>,
+
+    /// Needs file of the base, every need changed since then is taken as changed
+    #[arg(long)]
+    base: Option<PathBuf>,
+
+    /// Version of the base file compared, its `current_version` when not given
+    #[arg(long, requires = "base")]
+    base_version: Option<String>,
+
+    /// Number of links followed from the changed needs, unlimited when not given
+    #[arg(long)]
+    depth: Option<usize>,
+
+    /// Link direction followed from the changed needs, `both` walks up and down separately
+    #[arg(long, value_enum, default_value_t = LinkDirection::Both)]
+    direction: LinkDirection,
+
+    /// Link types followed, all of them when not given
+    #[arg(long, value_delimiter = ',')]
+    link_types: Vec<LinkType>,
+
+    /// Types of the affected needs listed, e.g `test`, all of them when not given
+    #[arg(long, value_delimiter = ',')]
+    kinds: Vec<NodeKind>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+    format: DiffFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffFormat {
+    /// Colored human readable output
+    Text,
+    /// Single JSON document on stdout
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum LinkDirection {
+    /// Follow outgoing links
+    Forward,
+    /// Follow incoming links
+    Backward,
+    /// Follow links either way
+    Both,
+}
+
+impl From<LinkDirection> for Direction {
+    fn from(d: LinkDirection) -> Self {
+        match d {
+            LinkDirection::Forward => Direction::Forward,
+            LinkDirection::Backward => Direction::Backward,
+            LinkDirection::Both => Direction::Both,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+struct InputArgs {
     /// Path to needs file (Sphinx-Needs JSON)
     #[arg(long)]
     needs: PathBuf,
@@ -30,24 +387,510 @@ struct Args {
     /// Path to policies file (JSON)
     #[arg(long)]
     policies: PathBuf,
+
+    /// Metamodel file, compiled into rules checked together with the policy
+    #[arg(long)]
+    metamodel: Option<PathBuf>,
+
+    /// Refuse to evaluate a policy with unknown rules or invalid params
+    #[arg(long)]
+    strict: bool,
+}
+
+#[derive(Args, Debug)]
+struct CheckArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Sphinx source dir relative to the repository root, prefixes SARIF document paths
+    #[arg(long)]
+    docs_root: Option<String>,
+
+    /// Exit with 1 when any issue of this severity or higher is found
+    #[arg(long, value_enum)]
+    fail_on: Option<FailOn>,
+
+    /// Issue or rule code which never fails the run (repeatable)
+    #[arg(long = "allow", value_name = "CODE")]
+    allow: Vec<String>,
+
+    /// Issue or rule code which always fails the run (repeatable)
+    #[arg(long = "deny", value_name = "CODE")]
+    deny: Vec<String>,
+
+    /// Baseline file, issues recorded in it are not reported
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Waiver file with justified and time limited deviations
+    #[arg(long)]
+    waivers: Option<PathBuf>,
+
+    /// Review state file, links whose upstream need changed since the review are reported as suspect
+    #[arg(long)]
+    review_state: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match (&cli.command, &cli.inputs) {
+        (None, Some(inputs)) => run_check(inputs, &cli.check),
+        (None, None) => unreachable!("clap requires --needs and --policies without a subcommand"),
+        (
+            Some(Command::Baseline {
+                action:
+                    BaselineCommand::Write {
+                        inputs,
+                        output,
+                        review_state,
+                        waivers,
+                    },
+            }),
+            _,
+        ) => {
+            write_baseline(inputs, output, review_state.as_deref(), waivers.as_deref()).map(|()| 0)
+        }
+        (Some(Command::Matrix(args)), _) => write_matrix(args).map(|()| 0),
+        (Some(Command::Graph(args)), _) => write_graph(args).map(|()| 0),
+        (Some(Command::Diff(args)), _) => run_diff(args).map(|()| 0),
+        (Some(Command::Changes(args)), _) => run_changes(args),
+        (Some(Command::Review(args)), _) => write_review(args).map(|()| 0),
+        (Some(Command::Impact(args)), _) => run_impact(args).map(|()| 0),
+    };
+
+    match result {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(failing) => {
+            eprintln!("{failing} issue(s) failed the run");
+            ExitCode::from(EXIT_VIOLATIONS)
+        }
+        Err(err) => {
+            eprintln!("{}", cli_printers::error(&err, ColorMode::Always));
+            ExitCode::from(EXIT_LOAD_ERROR)
+        }
+    }
+}
+
+// Loads the policy and the graph and validates the policy against the need
+// types of the export. Diagnostics are printed when `verbose` - for machine
+// readable outputs they go to stderr in strict mode only.
+fn load_inputs(
+    inputs: &InputArgs,
+    verbose: bool,
+) -> core_needle::Result<(Policies, Graph, Vec<Diagnostic>)> {
+    let loaded_policy = load_policy(inputs)?;
+    // Policy might declare link and need types which are not discoverable from the export
+    let loaded_graph = load_graph_for_policy(inputs.needs.as_path(), &loaded_policy)?;
+    let diagnostics = check_policy(inputs, &loaded_policy, &loaded_graph, verbose)?;
+
+    Ok((loaded_policy, loaded_graph, diagnostics))
 }
 
-fn main() {
-    let args = Args::parse();
+// Policy with the rules compiled from the metamodel
+fn load_policy(inputs: &InputArgs) -> core_needle::Result<Policies> {
+    let mut loaded_policy: Policies = load_policy_from_file(inputs.policies.as_path())?;
+    if let Some(path) = &inputs.metamodel {
+        let metamodel = load_metamodel_from_file(path)?;
+        loaded_policy.rules.extend(metamodel.compile());
+    }
+    Ok(loaded_policy)
+}
+
+fn check_policy(
+    inputs: &InputArgs,
+    loaded_policy: &Policies,
+    loaded_graph: &Graph,
+    verbose: bool,
+) -> core_needle::Result<Vec<Diagnostic>> {
+    let diagnostics = validate_for(loaded_policy, loaded_graph);
+    if !diagnostics.is_empty() && (verbose || inputs.strict) {
+        let severity = if inputs.strict {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+        // Keep stdout a valid document for machine readable formats
+        let printed = cli_printers::diagnostics(&diagnostics, severity, ColorMode::Always);
+        if verbose {
+            println!("{printed}");
+        } else {
+            eprintln!("{printed}");
+        }
+    }
+    if !diagnostics.is_empty() && inputs.strict {
+        return Err(core_needle::Error::InvalidPolicy {
+            problems: diagnostics.len(),
+        });
+    }
+    Ok(diagnostics)
+}
+
+// Suspect links are graph issues, they can be waived and baselined as any other
+fn add_suspects(loaded_graph: &mut Graph, review_state: Option<&Path>) -> core_needle::Result<()> {
+    if let Some(path) = review_state {
+        let state = load_review_state_from_file(path)?;
+        let suspects = state.suspects(loaded_graph);
+        loaded_graph.issues.extend(suspects);
+    }
+    Ok(())
+}
+
+fn write_baseline(
+    inputs: &InputArgs,
+    output: &Path,
+    review_state: Option<&Path>,
+    waivers: Option<&Path>,
+) -> core_needle::Result<()> {
+    let (loaded_policy, mut loaded_graph, _) = load_inputs(inputs, false)?;
+    let mut reports = evaluate_rules(&loaded_graph, &loaded_policy)?;
+    add_suspects(&mut loaded_graph, review_state)?;
+
+    // Waived issues are accepted already, like `check` they aren't baselined
+    let file = match waivers {
+        Some(path) => Some(load_waivers_from_file(path)?),
+        None => None,
+    };
+    Waivers::new(file).with_graph(&loaded_graph).apply(
+        &mut loaded_graph.issues,
+        &mut reports,
+        Date::today(),
+    );
+
+    let baseline = Baseline::from_issues(&loaded_graph.issues, &reports);
+    save_baseline_to_file(output, &baseline)?;
+    println!(
+        "Baseline with {} entries written to {}",
+        baseline.entries.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+fn write_matrix(args: &MatrixArgs) -> core_needle::Result<()> {
+    let loaded_graph = match &args.policies {
+        Some(path) => load_graph_for_policy(args.needs.as_path(), &load_policy_from_file(path)?)?,
+        None => load_graph_from_file(args.needs.as_path())?,
+    };
+    let options = MatrixOptions {
+        max_hops: (args.max_hops > 0).then_some(args.max_hops),
+        link_types: args.link_types.clone(),
+        direction: args.direction.into(),
+    };
+    let matrix = Matrix::build(&loaded_graph, &args.source, &args.targets, &options);
+    let report = MatrixReport::new(&matrix, args.format).to_string();
+
+    match &args.output {
+        Some(path) => {
+            save_report_to_file(path, &report)?;
+            println!(
+                "Matrix of {} {} needs ({} uncovered) written to {}",
+                matrix.rows.len(),
+                matrix.source,
+                matrix.uncovered(),
+                path.display()
+            );
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+fn write_graph(args: &GraphArgs) -> core_needle::Result<()> {
+    let loaded_policy = match &args.policies {
+        Some(path) => Some(load_policy_from_file(path)?),
+        None => None,
+    };
+    let loaded_graph = match &loaded_policy {
+        Some(policy) => load_graph_for_policy(args.needs.as_path(), policy)?,
+        None => load_graph_from_file(args.needs.as_path())?,
+    };
+    let reports = match &loaded_policy {
+        Some(policy) => evaluate_rules(&loaded_graph, policy)?,
+        None => Vec::new(),
+    };
+
+    let options = SubgraphOptions {
+        roots: args.roots.clone(),
+        depth: args.depth,
+        kinds: args.kinds.clone(),
+        link_types: args.link_types.clone(),
+        direction: args.direction.into(),
+    };
+    let subgraph = Subgraph::extract(&loaded_graph, &options)?;
+    let diagram = GraphExport::new(&loaded_graph, &subgraph, args.format)
+        .with_issues(
+            loaded_graph
+                .issues
+                .iter()
+                .chain(reports.iter().flat_map(|r| &r.issues)),
+        )
+        .to_string();
+
+    match &args.output {
+        Some(path) => {
+            save_report_to_file(path, &diagram)?;
+            println!(
+                "Graph of {} needs and {} links written to {}",
+                subgraph.nodes.len(),
+                subgraph.edges.len(),
+                path.display()
+            );
+        }
+        None => print!("{diagram}"),
+    }
+    Ok(())
+}
+
+fn run_diff(args: &DiffArgs) -> core_needle::Result<()> {
+    let base_set = populate_from_file(args.base.as_path())?;
+    let head_set = match &args.head {
+        Some(path) => Some(populate_from_file(path)?),
+        None => None,
+    };
+    let base = access(&base_set, args.base_version.as_deref())?;
+    let head = access(
+        head_set.as_ref().unwrap_or(&base_set),
+        args.head_version.as_deref(),
+    )?;
+
+    let mut options = DiffOptions::default();
+    options
+        .ignore_fields
+        .extend(args.ignore_fields.iter().cloned());
+    let diff = Diff::between(&base, &head, &options);
+
+    match args.format {
+        DiffFormat::Text => print!("{}", cli_printers::diff(&diff, ColorMode::Always)),
+        DiffFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&diff).expect("diff is serializable")
+        ),
+    }
+    Ok(())
+}
+
+// Returns the number of introduced issues which failed the gate
+fn run_changes(args: &ChangesArgs) -> core_needle::Result<usize> {
+    let text = args.format == DiffFormat::Text;
+    let loaded_policy = load_policy(&args.inputs)?;
+
+    let head_set = populate_from_file(args.inputs.needs.as_path())?;
+    let head = head_set.access_current_version()?;
+    let head_graph = graph_for_policy(head, &loaded_policy)?;
+    check_policy(&args.inputs, &loaded_policy, &head_graph, text)?;
+
+    let base_set = populate_from_file(args.base.as_path())?;
+    let base = access(&base_set, args.base_version.as_deref())?;
+    let base_graph = graph_for_policy(base, &loaded_policy)?;
+
+    let diff = Diff::between(&base, &head, &DiffOptions::default());
+    let report = ChangeReport::evaluate(&base_graph, &head_graph, &loaded_policy, &diff)?;
+
+    let gate = Gate {
+        fail_on: args.fail_on.map(Severity::from),
+        allow: args.allow.clone(),
+        deny: args.deny.clone(),
+    };
+    let failing = report
+        .introduced
+        .iter()
+        .filter(|i| gate.fails(&i.issue, Some(&i.rule_code)))
+        .count();
+
+    match args.format {
+        DiffFormat::Text => {
+            print!("{}", cli_printers::diff(&diff, ColorMode::Always));
+            print!("{}", cli_printers::changes(&report, ColorMode::Always));
+        }
+        DiffFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("report is serializable")
+        ),
+    }
+    Ok(failing)
+}
+
+fn write_review(args: &ReviewArgs) -> core_needle::Result<()> {
+    let loaded_graph = match &args.policies {
+        Some(path) => load_graph_for_policy(args.needs.as_path(), &load_policy_from_file(path)?)?,
+        None => load_graph_from_file(args.needs.as_path())?,
+    };
+
+    let mut state = if args.state.exists() {
+        if !args.fields.is_empty() || !args.link_types.is_empty() {
+            eprintln!("--fields and --link-types are kept from the existing state file");
+        }
+        load_review_state_from_file(&args.state)?
+    } else {
+        let mut state = ReviewState::default();
+        if !args.fields.is_empty() {
+            state.fields = args.fields.clone();
+        }
+        state.link_types = args.link_types.clone();
+        state
+    };
+
+    let ids: Vec<&NodeId> = if args.ids.is_empty() {
+        let mut all: Vec<&NodeId> = loaded_graph.nodes.keys().collect();
+        all.sort();
+        all
+    } else {
+        args.ids.iter().collect()
+    };
+    let mut links = 0;
+    for id in &ids {
+        if !loaded_graph.nodes.contains_key(*id) {
+            return Err(core_needle::Error::UnknownNeed { id: (*id).clone() });
+        }
+        links += state.review(&loaded_graph, id);
+    }
+
+    save_review_state_to_file(&args.state, &state)?;
+    println!(
+        "Reviewed {} link(s) of {} need(s), state written to {}",
+        links,
+        ids.len(),
+        args.state.display()
+    );
+    Ok(())
+}
+
+fn run_impact(args: &ImpactArgs) -> core_needle::Result<()> {
+    let loaded_policy = match &args.policies {
+        Some(path) => Some(load_policy_from_file(path)?),
+        None => None,
+    };
+    let head_set = populate_from_file(args.needs.as_path())?;
+    let head = head_set.access_current_version()?;
+    let loaded_graph = match &loaded_policy {
+        Some(policy) => graph_for_policy(head, policy)?,
+        None => Graph::new(head)?,
+    };
+
+    let options = ImpactOptions {
+        depth: args.depth,
+        link_types: args.link_types.clone(),
+        direction: args.direction.into(),
+        kinds: args.kinds.clone(),
+    };
+    let mut changed = args.ids.clone();
+    if let Some(path) = &args.base {
+        let base_set = populate_from_file(path)?;
+        let base = access(&base_set, args.base_version.as_deref())?;
+        let diff = Diff::between(&base, &head, &DiffOptions::default());
+        changed.extend(Impact::of_diff(&loaded_graph, &diff, &options).changed);
+    }
+    let impact = Impact::of(&loaded_graph, &changed, &options)?;
+
+    match args.format {
+        DiffFormat::Text => print!("{}", cli_printers::impact(&impact)),
+        DiffFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&impact).expect("impact is serializable")
+        ),
+    }
+    Ok(())
+}
+
+// Given version of the export, `current_version` without one
+fn access<'a>(
+    dataset: &'a Dataset,
+    version: Option<&'a str>,
+) -> core_needle::Result<VersionAccessor<'a>> {
+    match version {
+        Some(version) => dataset.access_version(version),
+        None => dataset.access_current_version(),
+    }
+}
+
+// Returns the number of issues which failed the gate
+fn run_check(inputs: &InputArgs, args: &CheckArgs) -> core_needle::Result<usize> {
+    let text = args.format == OutputFormat::Text;
+
+    if text {
+        println!("Needs file: {}", inputs.needs.display());
+        println!("Policies file: {}", inputs.policies.display());
+    }
+
+    let (loaded_policy, mut loaded_graph, diagnostics) = load_inputs(inputs, text)?;
+    let mut reports: Vec<RuleReport<'_>> = evaluate_rules(&loaded_graph, &loaded_policy)?;
+
+    add_suspects(&mut loaded_graph, args.review_state.as_deref())?;
+
+    // Waivers are applied first, issues they accept are audited, not baselined
+    let file = match &args.waivers {
+        Some(path) => Some(load_waivers_from_file(path)?),
+        None => None,
+    };
+    let waivers: WaiverOutcome = Waivers::new(file).with_graph(&loaded_graph).apply(
+        &mut loaded_graph.issues,
+        &mut reports,
+        Date::today(),
+    );
 
-    println!("Needs file: {}", args.needs.display());
-    println!("Policies file: {}", args.policies.display());
+    let baseline: Option<BaselineOutcome> = match &args.baseline {
+        Some(path) => {
+            let known = load_baseline_from_file(path)?;
+            Some(known.apply(&mut loaded_graph.issues, &mut reports))
+        }
+        None => None,
+    };
 
-    let loaded_graph =
-        load_graph_from_file(args.needs.as_path()).expect("Can't load graph from file");
+    if text {
+        println!("Summary of loaded graph");
+        println!("{}", cli_printers::issues(&loaded_graph, ColorMode::Always));
+        println!("Running evaluation of the policy!");
+    }
 
-    let loaded_policy =
-        load_policy_from_file(args.policies.as_path()).expect("Can't load policy from file");
+    let gate = Gate {
+        fail_on: args.fail_on.map(Severity::from),
+        allow: args.allow.clone(),
+        deny: args.deny.clone(),
+    };
+    let failing = gate.count(&loaded_graph.issues, &reports);
 
-    println!("Summary of loaded graph");
-    println!("{}", cli_printers::issues(&loaded_graph, ColorMode::Always));
+    match args.format {
+        OutputFormat::Text => {
+            if reports.iter().any(|r| r.metric.is_some()) {
+                println!("{}", cli_printers::metrics(&reports, ColorMode::Always));
+            }
+            let issues: Vec<Issue> = reports.into_iter().flat_map(|r| r.issues).collect();
+            println!("{}", cli_printers::issues_from(&issues, ColorMode::Always));
+            if !waivers.applied.is_empty() {
+                println!("{}", cli_printers::waivers(&waivers, ColorMode::Always));
+            }
+            if let Some(outcome) = &baseline {
+                println!("{}", cli_printers::baseline(outcome, ColorMode::Always));
+            }
+        }
+        OutputFormat::Json => {
+            let report = JsonReport::new(&diagnostics, &loaded_graph.issues, &reports)
+                .with_baseline(baseline.as_ref())
+                .with_waivers(Some(&waivers));
+            println!(
+                "{}",
+                report.to_string_pretty().expect("report is serializable")
+            );
+        }
+        OutputFormat::Sarif => {
+            let log = SarifLog::new(
+                &loaded_graph,
+                &loaded_policy.rules,
+                &loaded_policy.defaults,
+                &reports,
+                args.docs_root.as_deref(),
+            );
+            println!(
+                "{}",
+                log.to_string_pretty().expect("report is serializable")
+            );
+        }
+        OutputFormat::Junit => {
+            print!("{}", JunitReport::new(&loaded_graph, &reports));
+        }
+    }
 
-    println!("Running evaluation of the policy!");
-    let issues: Vec<Issue> = evaluate(&loaded_graph, &loaded_policy);
-    println!("{}", cli_printers::issues_from(&issues, ColorMode::Always));
+    Ok(failing)
 }
@@ -0,0 +1,331 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use std::collections::HashMap;
+use std::fmt;
+
+use clap::ValueEnum;
+use core_needle::representation::{Graph, Issue, NodeId, NodeKind, Severity};
+use core_needle::subgraph::Subgraph;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// PlantUML diagram
+    Plantuml,
+}
+
+// Fill colors handed out to the need types in order, named colors are
+// understood by Graphviz, Mermaid (CSS) and PlantUML alike
+const PALETTE: &[&str] = &[
+    "lightblue",
+    "palegreen",
+    "lightyellow",
+    "lightpink",
+    "lavender",
+    "peachpuff",
+    "lightcyan",
+    "wheat",
+    "thistle",
+    "lightgray",
+];
+
+fn outline(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "red",
+        Severity::Warning => "orange",
+        Severity::Suggestion => "royalblue",
+    }
+}
+
+// Subgraph as a diagram. Needs are filled by type and outlined by the worst
+// severity of their issues, links are labelled by their type.
+pub struct GraphExport<'a> {
+    graph: &'a Graph,
+    subgraph: &'a Subgraph<'a>,
+    format: GraphFormat,
+    kinds: Vec<NodeKind>,
+    worst: HashMap<&'a NodeId, Severity>,
+}
+
+impl<'a> GraphExport<'a> {
+    pub fn new(graph: &'a Graph, subgraph: &'a Subgraph<'a>, format: GraphFormat) -> Self {
+        Self {
+            graph,
+            subgraph,
+            format,
+            kinds: subgraph.kinds(graph),
+            worst: HashMap::new(),
+        }
+    }
+
+    pub fn with_issues(mut self, issues: impl IntoIterator<Item = &'a Issue>) -> Self {
+        for issue in issues {
+            let worst = self.worst.entry(&issue.subject).or_insert(issue.severity);
+            *worst = (*worst).max(issue.severity);
+        }
+        self
+    }
+
+    fn kind(&self, id: &NodeId) -> Option<NodeKind> {
+        self.graph.kind_of(id)
+    }
+
+    fn fill(&self, kind: &NodeKind) -> &'static str {
+        let idx = self.kinds.iter().position(|k| k == kind).unwrap_or(0);
+        PALETTE[idx % PALETTE.len()]
+    }
+
+    // e.g `REQ_1 (req)` and the title below it
+    fn label(&self, id: &NodeId) -> (String, Option<String>) {
+        let head = match self.kind(id) {
+            Some(kind) => format!("{id} ({kind})"),
+            None => id.to_string(),
+        };
+        let title = self
+            .graph
+            .nodes
+            .get(id)
+            .and_then(|n| n.title.as_deref())
+            .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|t| !t.is_empty());
+        (head, title)
+    }
+
+    // Diagram ids, need ids may contain characters Mermaid and PlantUML reject
+    fn alias(&self, id: &NodeId) -> String {
+        let idx = self
+            .subgraph
+            .nodes
+            .binary_search(&id)
+            .expect("edges only join listed needs");
+        format!("n{idx}")
+    }
+
+    fn dot(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph needs {{")?;
+        writeln!(f, "  rankdir=LR;")?;
+        writeln!(
+            f,
+            r#"  node [shape=box, style="rounded,filled", fontname="Helvetica"];"#
+        )?;
+        writeln!(f, r#"  edge [fontname="Helvetica", fontsize=10];"#)?;
+        for id in &self.subgraph.nodes {
+            let (head, title) = self.label(id);
+            let label = match title {
+                Some(title) => format!("{}\\n{}", dot_escape(&head), dot_escape(&title)),
+                None => dot_escape(&head),
+            };
+            let mut attrs = vec![format!("label=\"{label}\"")];
+            if let Some(kind) = self.kind(id) {
+                attrs.push(format!("fillcolor={}", self.fill(&kind)));
+            }
+            if let Some(severity) = self.worst.get(id) {
+                attrs.push(format!("color={}, penwidth=3", outline(*severity)));
+            }
+            writeln!(
+                f,
+                "  \"{}\" [{}];",
+                dot_escape(id.as_ref()),
+                attrs.join(", ")
+            )?;
+        }
+        for e in &self.subgraph.edges {
+            writeln!(
+                f,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                dot_escape(e.from.as_ref()),
+                dot_escape(e.to.as_ref()),
+                dot_escape(e.link_type.as_ref())
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+
+    fn mermaid(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "flowchart LR")?;
+        for id in &self.subgraph.nodes {
+            let (head, title) = self.label(id);
+            let label = match title {
+                Some(title) => format!("{}<br/>{}", mermaid_escape(&head), mermaid_escape(&title)),
+                None => mermaid_escape(&head),
+            };
+            writeln!(f, "  {}[\"{label}\"]", self.alias(id))?;
+        }
+        for e in &self.subgraph.edges {
+            writeln!(
+                f,
+                "  {} -->|{}| {}",
+                self.alias(e.from),
+                mermaid_escape(e.link_type.as_ref()),
+                self.alias(e.to)
+            )?;
+        }
+
+        for (idx, kind) in self.kinds.iter().enumerate() {
+            let members: Vec<String> = self
+                .subgraph
+                .nodes
+                .iter()
+                .filter(|id| self.kind(id).as_ref() == Some(kind))
+                .map(|id| self.alias(id))
+                .collect();
+            writeln!(f, "  classDef kind{idx} fill:{}", self.fill(kind))?;
+            writeln!(f, "  class {} kind{idx}", members.join(","))?;
+        }
+        for severity in [Severity::Error, Severity::Warning, Severity::Suggestion] {
+            let members: Vec<String> = self
+                .subgraph
+                .nodes
+                .iter()
+                .filter(|id| self.worst.get(*id) == Some(&severity))
+                .map(|id| self.alias(id))
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            writeln!(
+                f,
+                "  classDef {severity} stroke:{},stroke-width:3px",
+                outline(severity)
+            )?;
+            writeln!(f, "  class {} {severity}", members.join(","))?;
+        }
+        Ok(())
+    }
+
+    fn plantuml(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "@startuml")?;
+        writeln!(f, "left to right direction")?;
+        for id in &self.subgraph.nodes {
+            let (head, title) = self.label(id);
+            let label = match title {
+                Some(title) => format!("{}\\n{}", plantuml_escape(&head), plantuml_escape(&title)),
+                None => plantuml_escape(&head),
+            };
+            let mut style = String::new();
+            if let Some(kind) = self.kind(id) {
+                style.push_str(&format!(" #{}", self.fill(&kind)));
+            }
+            if let Some(severity) = self.worst.get(id) {
+                let sep = if style.is_empty() { " #" } else { ";" };
+                style.push_str(&format!("{sep}line:{};line.bold", outline(*severity)));
+            }
+            writeln!(f, "rectangle \"{label}\" as {}{style}", self.alias(id))?;
+        }
+        for e in &self.subgraph.edges {
+            writeln!(
+                f,
+                "{} --> {} : {}",
+                self.alias(e.from),
+                self.alias(e.to),
+                plantuml_escape(e.link_type.as_ref())
+            )?;
+        }
+        writeln!(f, "@enduml")
+    }
+}
+
+impl fmt::Display for GraphExport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.format {
+            GraphFormat::Dot => self.dot(f),
+            GraphFormat::Mermaid => self.mermaid(f),
+            GraphFormat::Plantuml => self.plantuml(f),
+        }
+    }
+}
+
+fn dot_escape(raw: &str) -> String {
+    raw.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Mermaid entity codes, plain quotes and brackets end the label
+fn mermaid_escape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '"' => out.push_str("#quot;"),
+            '<' => out.push_str("#lt;"),
+            '>' => out.push_str("#gt;"),
+            '|' => out.push_str("#124;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// PlantUML strings have no escape for quotes
+fn plantuml_escape(raw: &str) -> String {
+    raw.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_needle::io::{load_policy_from_str, populate_from_str};
+    use core_needle::policy::evaluate_rules;
+    use core_needle::subgraph::SubgraphOptions;
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "REQ_1":  { "id":"REQ_1","type":"req","title":"Stop \"fast\"","links":["TEST_1"] },
+            "REQ_2":  { "id":"REQ_2","type":"req","links":[] },
+            "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+          }
+        }
+      }
+    }"#;
+
+    const POLICY: &str = r#"
+    { "version": 1, "rules": [
+        { "rule_id": "has_outgoing", "selector": { "kinds": ["req"] } },
+        { "rule_id": "field_present", "selector": { "kinds": ["req"] },
+          "params": { "field": "status" }, "severity": "warning" }
+    ] }"#;
+
+    #[test]
+    fn renders_dot_mermaid_and_plantuml() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(POLICY).unwrap();
+        let reports = evaluate_rules(&g, &policy).unwrap();
+        let sub = Subgraph::extract(&g, &SubgraphOptions::default()).unwrap();
+        let export = |format| {
+            GraphExport::new(&g, &sub, format)
+                .with_issues(reports.iter().flat_map(|r| &r.issues))
+                .to_string()
+        };
+
+        let dot = export(GraphFormat::Dot);
+        assert!(dot.contains(
+            r#""REQ_1" [label="REQ_1 (req)\nStop \"fast\"", fillcolor=lightblue, color=orange, penwidth=3];"#
+        ));
+        assert!(dot.contains(
+            r#""REQ_2" [label="REQ_2 (req)", fillcolor=lightblue, color=red, penwidth=3];"#
+        ));
+        assert!(dot.contains(r#""TEST_1" [label="TEST_1 (test)", fillcolor=palegreen];"#));
+        assert!(dot.contains(r#""REQ_1" -> "TEST_1" [label="links"];"#));
+
+        let mermaid = export(GraphFormat::Mermaid);
+        assert!(mermaid.contains(r#"  n0["REQ_1 (req)<br/>Stop #quot;fast#quot;"]"#));
+        assert!(mermaid.contains("  n0 -->|links| n2\n"));
+        assert!(mermaid.contains("  class n0,n1 kind0\n"));
+        assert!(mermaid.contains("  class n1 error\n  classDef warning"));
+
+        let puml = export(GraphFormat::Plantuml);
+        assert!(puml.contains(r#"rectangle "REQ_2 (req)" as n1 #lightblue;line:red;line.bold"#));
+        assert!(puml.contains("n0 --> n2 : links"));
+        assert!(puml.ends_with("@enduml\n"));
+    }
+}
@@ -0,0 +1,224 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use core_needle::baseline::BaselineOutcome;
+use core_needle::policy::{coded_issues, Diagnostic, Metric, RuleReport};
+use core_needle::representation::{Issue, Severity};
+use core_needle::waiver::WaiverOutcome;
+
+// Single JSON document with everything a run produced. Graph issues come from
+// building the graph, policy issues from evaluating the rules.
+#[derive(Serialize)]
+pub struct JsonReport<'a> {
+    pub diagnostics: &'a [Diagnostic],
+    pub graph_issues: &'a [Issue],
+    pub policy_issues: Vec<PolicyIssue<'a>>,
+    // Values measured by aggregate rules, whether their threshold holds or not
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metrics: Vec<RuleMetric<'a>>,
+    pub summary: Summary,
+    // Present when run with `--baseline`, summary counts only non-suppressed issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<&'a BaselineOutcome>,
+    // Suppressed issues together with the waiver which accepted them, for audit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waivers: Option<&'a WaiverOutcome>,
+}
+
+#[derive(Serialize)]
+pub struct RuleRef<'a> {
+    pub index: usize,
+    pub rule_id: &'a str,
+    pub code: Option<&'a str>,
+    pub message: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+pub struct PolicyIssue<'a> {
+    pub rule: RuleRef<'a>,
+    #[serde(flatten)]
+    pub issue: &'a Issue,
+}
+
+#[derive(Serialize)]
+pub struct RuleMetric<'a> {
+    pub rule: RuleRef<'a>,
+    #[serde(flatten)]
+    pub metric: &'a Metric,
+}
+
+#[derive(Serialize)]
+pub struct Summary {
+    pub total: usize,
+    pub by_severity: BTreeMap<&'static str, usize>,
+    // Issue code for graph issues, rule code for policy issues, lowercase
+    pub by_code: BTreeMap<String, usize>,
+}
+
+impl<'a> JsonReport<'a> {
+    pub fn new(
+        diagnostics: &'a [Diagnostic],
+        graph_issues: &'a [Issue],
+        reports: &'a [RuleReport<'_>],
+    ) -> Self {
+        let policy_issues: Vec<PolicyIssue<'a>> = reports
+            .iter()
+            .flat_map(|r| {
+                r.issues.iter().map(move |issue| PolicyIssue {
+                    rule: RuleRef::of(r),
+                    issue,
+                })
+            })
+            .collect();
+
+        let metrics: Vec<RuleMetric<'a>> = reports
+            .iter()
+            .filter_map(|r| {
+                r.metric.as_ref().map(|metric| RuleMetric {
+                    rule: RuleRef::of(r),
+                    metric,
+                })
+            })
+            .collect();
+
+        let summary = Summary::new(coded_issues(graph_issues, reports));
+
+        Self {
+            diagnostics,
+            graph_issues,
+            policy_issues,
+            metrics,
+            summary,
+            baseline: None,
+            waivers: None,
+        }
+    }
+
+    pub fn with_baseline(mut self, baseline: Option<&'a BaselineOutcome>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    pub fn with_waivers(mut self, waivers: Option<&'a WaiverOutcome>) -> Self {
+        self.waivers = waivers;
+        self
+    }
+
+    pub fn to_string_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl<'a> RuleRef<'a> {
+    fn of(report: &'a RuleReport<'_>) -> Self {
+        Self {
+            index: report.index,
+            rule_id: &report.rule.rule_id,
+            code: report.rule.code.as_deref(),
+            message: report.rule.message.as_deref(),
+        }
+    }
+}
+
+impl Summary {
+    fn new<'i>(issues: impl Iterator<Item = (&'i str, &'i Issue)>) -> Self {
+        let mut by_severity: BTreeMap<&'static str, usize> =
+            [Severity::Error, Severity::Warning, Severity::Suggestion]
+                .into_iter()
+                .map(|s| (s.to_str(), 0))
+                .collect();
+        let mut by_code: BTreeMap<String, usize> = BTreeMap::new();
+        let mut total = 0;
+
+        for (code, issue) in issues {
+            total += 1;
+            *by_severity.entry(issue.severity.to_str()).or_default() += 1;
+            *by_code.entry(code.to_ascii_lowercase()).or_default() += 1;
+        }
+
+        Self {
+            total,
+            by_severity,
+            by_code,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_needle::policy::Rule;
+    use core_needle::representation::{IssueCode, NodeId};
+
+    #[test]
+    fn report_attributes_rule_and_counts() {
+        let rule: Rule = serde_json::from_str(
+            r#"{ "rule_id": "has_outgoing", "selector": { "kinds": ["impl"] }, "code": "IMPL_NO_LINKS" }"#,
+        )
+        .unwrap();
+        let graph_issues = vec![Issue::error(
+            IssueCode::BrokenLink,
+            NodeId::from("REQ_1"),
+            "broken",
+        )];
+        let reports = vec![RuleReport {
+            index: 0,
+            rule: &rule,
+            issues: vec![Issue::warn(
+                IssueCode::ImplNoLinks,
+                NodeId::from("IMPL_1"),
+                "no links",
+            )],
+            metric: None,
+        }];
+
+        let report = JsonReport::new(&[], &graph_issues, &reports);
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["policy_issues"][0]["rule"]["rule_id"], "has_outgoing");
+        assert_eq!(json["policy_issues"][0]["rule"]["code"], "IMPL_NO_LINKS");
+        assert_eq!(json["policy_issues"][0]["subject"], "IMPL_1");
+        assert_eq!(json["summary"]["total"], 2);
+        assert_eq!(json["summary"]["by_severity"]["error"], 1);
+        assert_eq!(json["summary"]["by_severity"]["suggestion"], 0);
+        assert_eq!(json["summary"]["by_code"]["impl_no_links"], 1);
+    }
+
+    #[test]
+    fn summary_counts_policy_issues_by_rule_code() {
+        let rules: Vec<Rule> = serde_json::from_str(
+            r#"[ { "rule_id": "field_present", "selector": { "kinds": ["req"] }, "code": "REQ_STATUS" },
+                 { "rule_id": "no_cycles", "selector": {} } ]"#,
+        )
+        .unwrap();
+        let issue = |subject: &str| Issue::error(IssueCode::Cycle, NodeId::from(subject), "x");
+        let reports = vec![
+            RuleReport {
+                index: 0,
+                rule: &rules[0],
+                issues: vec![issue("REQ_1"), issue("REQ_2")],
+                metric: None,
+            },
+            RuleReport {
+                index: 1,
+                rule: &rules[1],
+                issues: vec![issue("REQ_3")],
+                metric: None,
+            },
+        ];
+
+        let report = JsonReport::new(&[], &[], &reports);
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(
+            json["summary"]["by_code"],
+            serde_json::json!({ "req_status": 2, "no_cycles": 1 })
+        );
+    }
+}
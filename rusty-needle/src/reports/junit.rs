@@ -0,0 +1,211 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+use core_needle::policy::RuleReport;
+use core_needle::representation::{Graph, Issue, NodeId, Severity};
+
+// JUnit XML as understood by most CI dashboards. Every policy rule is a
+// <testsuite>, every node it selects a <testcase>. Errors fail the testcase,
+// warnings and suggestions are listed in its <system-out>.
+pub struct JunitReport<'a> {
+    suites: Vec<Suite<'a>>,
+}
+
+// Issues are kept with their code - the issue code for graph issues, the rule
+// code for policy issues
+struct Suite<'a> {
+    name: String,
+    cases: BTreeMap<&'a NodeId, Vec<(&'a str, &'a Issue)>>,
+}
+
+impl Suite<'_> {
+    fn failures(&self) -> usize {
+        self.cases
+            .values()
+            .filter(|issues| issues.iter().any(|(_, i)| i.severity == Severity::Error))
+            .count()
+    }
+}
+
+impl<'a> JunitReport<'a> {
+    pub fn new(graph: &'a Graph, reports: &'a [RuleReport<'a>]) -> Self {
+        let mut suites = Vec::with_capacity(reports.len() + 1);
+
+        // Issues found while building the graph, one testcase per need
+        let mut cases: BTreeMap<&NodeId, Vec<(&str, &Issue)>> =
+            graph.nodes.keys().map(|id| (id, Vec::new())).collect();
+        for issue in &graph.issues {
+            cases
+                .entry(&issue.subject)
+                .or_default()
+                .push((issue.code.to_str(), issue));
+        }
+        suites.push(Suite {
+            name: "graph".to_string(),
+            cases,
+        });
+
+        for report in reports {
+            let code = report.rule.code_or_id();
+            let mut cases: BTreeMap<&NodeId, Vec<(&str, &Issue)>> = report
+                .rule
+                .selector
+                .select(graph)
+                .into_iter()
+                .map(|id| (id, Vec::new()))
+                .collect();
+            for issue in &report.issues {
+                cases.entry(&issue.subject).or_default().push((code, issue));
+            }
+            suites.push(Suite {
+                name: format!("{}.{}", report.index, code),
+                cases,
+            });
+        }
+
+        Self { suites }
+    }
+}
+
+impl fmt::Display for JunitReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tests: usize = self.suites.iter().map(|s| s.cases.len()).sum();
+        let failures: usize = self.suites.iter().map(Suite::failures).sum();
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<testsuites name="rusty-needle" tests="{tests}" failures="{failures}" errors="0">"#
+        )?;
+        for suite in &self.suites {
+            writeln!(
+                f,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" skipped="0">"#,
+                escape(&suite.name),
+                suite.cases.len(),
+                suite.failures()
+            )?;
+            for (id, issues) in &suite.cases {
+                write_case(f, &suite.name, id, issues)?;
+            }
+            writeln!(f, "  </testsuite>")?;
+        }
+        writeln!(f, "</testsuites>")
+    }
+}
+
+fn write_case(
+    f: &mut fmt::Formatter<'_>,
+    suite: &str,
+    id: &NodeId,
+    issues: &[(&str, &Issue)],
+) -> fmt::Result {
+    let open = format!(
+        r#"    <testcase name="{}" classname="{}""#,
+        escape(id.as_ref()),
+        escape(suite)
+    );
+    if issues.is_empty() {
+        return writeln!(f, "{open}/>");
+    }
+    writeln!(f, "{open}>")?;
+
+    let mut out = String::new();
+    for (code, issue) in issues {
+        if issue.severity == Severity::Error {
+            writeln!(
+                f,
+                r#"      <failure type="{}" message="{}"/>"#,
+                escape(code),
+                escape(&issue.detail)
+            )?;
+        } else {
+            // String writes can't fail
+            let _ = writeln!(out, "[{}] {}: {}", issue.severity, code, issue.detail);
+        }
+    }
+    if !out.is_empty() {
+        writeln!(
+            f,
+            "      <system-out>{}</system-out>",
+            escape(out.trim_end())
+        )?;
+    }
+    writeln!(f, "    </testcase>")
+}
+
+fn escape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_needle::io::{load_policy_from_str, populate_from_str};
+    use core_needle::policy::evaluate_rules;
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "IMPL_1": { "id":"IMPL_1","type":"impl","links":["REQ_1"] },
+            "IMPL_2": { "id":"IMPL_2","type":"impl","links":[] },
+            "REQ_1":  { "id":"REQ_1","type":"req","links":[] }
+          }
+        }
+      }
+    }"#;
+
+    const POLICY: &str = r#"
+    {
+      "version": 1,
+      "rules": [
+        { "rule_id": "has_outgoing", "selector": { "kinds": ["impl"] },
+          "code": "IMPL_NO_LINKS", "message": "Implementation <impl> has no links" },
+        { "rule_id": "field_present", "selector": { "kinds": ["req"] },
+          "params": { "field": "status" }, "severity": "warning", "code": "REQ_STATUS" }
+      ]
+    }"#;
+
+    #[test]
+    fn rules_become_suites_and_nodes_testcases() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let graph = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(POLICY).unwrap();
+        let reports = evaluate_rules(&graph, &policy).unwrap();
+
+        let xml = JunitReport::new(&graph, &reports).to_string();
+
+        assert!(xml.contains(
+            r#"<testsuite name="0.IMPL_NO_LINKS" tests="2" failures="1" errors="0" skipped="0">"#
+        ));
+        assert!(xml.contains(r#"<testcase name="IMPL_1" classname="0.IMPL_NO_LINKS"/>"#));
+        assert!(xml.contains(r#"message="Implementation &lt;impl&gt; has no links""#));
+        assert!(xml.contains(
+            r#"<testsuite name="1.REQ_STATUS" tests="1" failures="0" errors="0" skipped="0">"#
+        ));
+        assert!(xml.contains(r#"<failure type="IMPL_NO_LINKS" "#));
+        assert!(
+            xml.contains("<system-out>[warning] REQ_STATUS: required field missing</system-out>")
+        );
+    }
+}
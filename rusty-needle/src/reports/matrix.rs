@@ -0,0 +1,244 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use std::fmt;
+
+use clap::ValueEnum;
+use core_needle::matrix::{Matrix, MatrixRow};
+use core_needle::representation::NodeKind;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixFormat {
+    /// Comma separated values, one row per source need
+    Csv,
+    /// GitHub flavoured Markdown table
+    Markdown,
+    /// Standalone HTML page, uncovered rows are highlighted
+    Html,
+}
+
+// One row per need of the source type, one column per target type. Cells
+// list the reached needs with the length of the path, e.g `TEST_1 (1)`.
+pub struct MatrixReport<'a> {
+    matrix: &'a Matrix,
+    format: MatrixFormat,
+}
+
+impl<'a> MatrixReport<'a> {
+    pub fn new(matrix: &'a Matrix, format: MatrixFormat) -> Self {
+        Self { matrix, format }
+    }
+
+    fn header(&self) -> Vec<String> {
+        let mut out = vec![self.matrix.source.to_string(), "title".to_string()];
+        out.extend(self.matrix.targets.iter().map(NodeKind::to_string));
+        out.push("covered".to_string());
+        out
+    }
+
+    fn cells(&self, row: &MatrixRow) -> Vec<String> {
+        let mut out = vec![
+            row.source.to_string(),
+            row.title.clone().unwrap_or_default(),
+        ];
+        for kind in &self.matrix.targets {
+            let reached: Vec<String> = row
+                .of_kind(kind)
+                .map(|r| format!("{} ({})", r.target, r.distance))
+                .collect();
+            out.push(if reached.is_empty() {
+                "-".to_string()
+            } else {
+                reached.join("; ")
+            });
+        }
+        out.push(if row.covered() { "yes" } else { "UNCOVERED" }.to_string());
+        out
+    }
+
+    fn csv(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |cells: Vec<String>| {
+            cells
+                .iter()
+                .map(|c| csv_escape(c))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        writeln!(f, "{}", line(self.header()))?;
+        for row in &self.matrix.rows {
+            writeln!(f, "{}", line(self.cells(row)))?;
+        }
+        Ok(())
+    }
+
+    fn markdown(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |cells: Vec<String>| {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|c| c.replace('|', "\\|").replace('\n', " "))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let header = self.header();
+        let rule = vec!["---".to_string(); header.len()];
+        writeln!(f, "{}", line(header))?;
+        writeln!(f, "{}", line(rule))?;
+        for row in &self.matrix.rows {
+            writeln!(f, "{}", line(self.cells(row)))?;
+        }
+        Ok(())
+    }
+
+    fn html(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html>")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, r#"<meta charset="utf-8">"#)?;
+        writeln!(f, "<title>{}</title>", escape(&self.title()))?;
+        writeln!(
+            f,
+            "<style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; padding: 4px 8px; }} tr.uncovered {{ background: #fdd; }}</style>"
+        )?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<h1>{}</h1>", escape(&self.title()))?;
+        writeln!(
+            f,
+            "<p>{} of {} needs uncovered</p>",
+            self.matrix.uncovered(),
+            self.matrix.rows.len()
+        )?;
+        writeln!(f, "<table>")?;
+        let header: Vec<String> = self
+            .header()
+            .iter()
+            .map(|c| format!("<th>{}</th>", escape(c)))
+            .collect();
+        writeln!(f, "<tr>{}</tr>", header.concat())?;
+        for row in &self.matrix.rows {
+            let cells: Vec<String> = self
+                .cells(row)
+                .iter()
+                .map(|c| format!("<td>{}</td>", escape(c)))
+                .collect();
+            if row.covered() {
+                writeln!(f, "<tr>{}</tr>", cells.concat())?;
+            } else {
+                writeln!(f, r#"<tr class="uncovered">{}</tr>"#, cells.concat())?;
+            }
+        }
+        writeln!(f, "</table>")?;
+        writeln!(f, "</body>")?;
+        writeln!(f, "</html>")
+    }
+
+    // e.g `req -> test, testrun (up to 2 links)`
+    fn title(&self) -> String {
+        let targets: Vec<String> = self
+            .matrix
+            .targets
+            .iter()
+            .map(NodeKind::to_string)
+            .collect();
+        let hops = match self.matrix.max_hops {
+            Some(1) => "direct links".to_string(),
+            Some(max) => format!("up to {max} links"),
+            None => "any distance".to_string(),
+        };
+        format!("{} -> {} ({hops})", self.matrix.source, targets.join(", "))
+    }
+}
+
+impl fmt::Display for MatrixReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.format {
+            MatrixFormat::Csv => self.csv(f),
+            MatrixFormat::Markdown => self.markdown(f),
+            MatrixFormat::Html => self.html(f),
+        }
+    }
+}
+
+// Quoted only when needed, as most spreadsheets do
+fn csv_escape(raw: &str) -> String {
+    if raw.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", raw.replace('"', "\"\""))
+    } else {
+        raw.to_string()
+    }
+}
+
+fn escape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_needle::io::populate_from_str;
+    use core_needle::matrix::MatrixOptions;
+    use core_needle::representation::Graph;
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "REQ_1":  { "id":"REQ_1","type":"req","title":"Brake, then <stop>","links":["TEST_1"] },
+            "REQ_2":  { "id":"REQ_2","type":"req","title":"A | B","links":[] },
+            "TEST_1": { "id":"TEST_1","type":"test","links":[] }
+          }
+        }
+      }
+    }"#;
+
+    fn matrix() -> Matrix {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let g = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        Matrix::build(
+            &g,
+            &NodeKind::from("req"),
+            &[NodeKind::from("test")],
+            &MatrixOptions {
+                max_hops: Some(1),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn renders_csv_markdown_and_html() {
+        let m = matrix();
+
+        assert_eq!(
+            MatrixReport::new(&m, MatrixFormat::Csv).to_string(),
+            "req,title,test,covered\n\
+             REQ_1,\"Brake, then <stop>\",TEST_1 (1),yes\n\
+             REQ_2,A | B,-,UNCOVERED\n"
+        );
+
+        let md = MatrixReport::new(&m, MatrixFormat::Markdown).to_string();
+        assert!(md.starts_with("| req | title | test | covered |\n| --- | --- | --- | --- |\n"));
+        assert!(md.contains("| REQ_2 | A \\| B | - | UNCOVERED |"));
+
+        let html = MatrixReport::new(&m, MatrixFormat::Html).to_string();
+        assert!(html.contains("<td>Brake, then &lt;stop&gt;</td>"));
+        assert!(html.contains(r#"<tr class="uncovered"><td>REQ_2</td>"#));
+        assert!(html.contains("<p>1 of 2 needs uncovered</p>"));
+    }
+}
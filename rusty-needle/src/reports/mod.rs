@@ -0,0 +1,13 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+// Machine readable outputs, the colored terminal output lives in `cli_printers`.
+
+pub mod graph;
+pub mod json;
+pub mod junit;
+pub mod matrix;
+pub mod sarif;
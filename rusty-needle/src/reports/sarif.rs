@@ -0,0 +1,354 @@
+// -----------------------------------------------------------------------------
+// Copyright (c) 2025 Hubert Liberacki <hliberacki@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+// -----------------------------------------------------------------------------
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use core_needle::policy::{Defaults, Rule, RuleReport};
+use core_needle::representation::{Graph, Issue, Severity};
+
+// Minimal subset of SARIF 2.1.0 - just enough for code-scanning viewers to
+// show each issue next to the need's source document.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    short_description: Message,
+    default_configuration: Configuration,
+}
+
+#[derive(Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    name: String,
+    kind: &'static str,
+}
+
+pub fn level(sev: Severity) -> &'static str {
+    match sev {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Suggestion => "note",
+    }
+}
+
+// Same severity fallback as the rules use for their issues
+fn rule_descriptor(rule: &Rule, defaults: &Defaults) -> ReportingDescriptor {
+    let id = rule.code_or_id();
+    ReportingDescriptor {
+        id: id.to_string(),
+        name: Some(rule.rule_id.clone()),
+        short_description: Message {
+            text: rule.message.clone().unwrap_or_else(|| id.to_string()),
+        },
+        default_configuration: Configuration {
+            level: level(
+                rule.severity
+                    .or(defaults.severity)
+                    .unwrap_or(Severity::Error),
+            ),
+        },
+    }
+}
+
+struct Builder<'g> {
+    graph: &'g Graph,
+    // Prefix for document paths, docnames are relative to the Sphinx source dir
+    docs_root: Option<&'g str>,
+    rules: Vec<ReportingDescriptor>,
+    by_id: HashMap<String, usize>,
+    results: Vec<SarifResult>,
+}
+
+impl<'g> Builder<'g> {
+    fn descriptor(&mut self, id: &str, make: impl FnOnce() -> ReportingDescriptor) -> usize {
+        if let Some(idx) = self.by_id.get(id) {
+            return *idx;
+        }
+        let idx = self.rules.len();
+        self.rules.push(make());
+        self.by_id.insert(id.to_string(), idx);
+        idx
+    }
+
+    fn result(&mut self, rule_id: &str, rule_index: usize, issue: &Issue) {
+        let node = self.graph.nodes.get(&issue.subject);
+        let physical_location = node.and_then(|n| n.doc_path()).map(|path| {
+            let uri = match self.docs_root {
+                Some(root) => format!("{}/{}", root.trim_end_matches('/'), path),
+                None => path,
+            };
+            PhysicalLocation {
+                artifact_location: ArtifactLocation { uri },
+                region: node
+                    .and_then(|n| n.lineno())
+                    .map(|start_line| Region { start_line }),
+            }
+        });
+
+        self.results.push(SarifResult {
+            rule_id: rule_id.to_string(),
+            rule_index,
+            level: level(issue.severity),
+            message: Message {
+                text: format!("{}: {}", issue.subject, issue.detail),
+            },
+            locations: vec![Location {
+                physical_location,
+                logical_locations: vec![LogicalLocation {
+                    name: issue.subject.to_string(),
+                    kind: "need",
+                }],
+            }],
+        });
+    }
+}
+
+impl SarifLog {
+    pub fn new(
+        graph: &Graph,
+        policy: &[Rule],
+        defaults: &Defaults,
+        reports: &[RuleReport<'_>],
+        docs_root: Option<&str>,
+    ) -> Self {
+        let mut b = Builder {
+            graph,
+            docs_root,
+            rules: Vec::new(),
+            by_id: HashMap::new(),
+            results: Vec::new(),
+        };
+
+        // Every policy rule is described even if it produced no results
+        for rule in policy {
+            b.descriptor(rule.code_or_id(), || rule_descriptor(rule, defaults));
+        }
+
+        for issue in &graph.issues {
+            let id = issue.code.to_str();
+            let idx = b.descriptor(id, || ReportingDescriptor {
+                id: id.to_string(),
+                name: None,
+                short_description: Message {
+                    text: format!("graph consistency check `{id}`"),
+                },
+                default_configuration: Configuration {
+                    level: level(issue.severity),
+                },
+            });
+            b.result(id, idx, issue);
+        }
+
+        for report in reports {
+            let id = report.rule.code_or_id();
+            let idx = b.descriptor(id, || rule_descriptor(report.rule, defaults));
+            for issue in &report.issues {
+                b.result(id, idx, issue);
+            }
+        }
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "rusty-needle",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://github.com/hliberacki/rusty-needle",
+                        rules: b.rules,
+                    },
+                },
+                results: b.results,
+            }],
+        }
+    }
+
+    pub fn to_string_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_needle::io::{load_policy_from_str, populate_from_str};
+
+    const NEEDS: &str = r#"
+    {
+      "current_version": "1.0",
+      "versions": {
+        "1.0": {
+          "needs": {
+            "IMPL_1": { "id":"IMPL_1","type":"impl","links":[],
+                        "docname":"adas/impl","doctype":".rst","lineno":7 }
+          }
+        }
+      }
+    }"#;
+
+    const POLICY: &str = r#"
+    {
+      "version": 1,
+      "defaults": { "severity": "suggestion" },
+      "rules": [
+        { "rule_id": "has_outgoing", "selector": { "kinds": ["impl"] },
+          "severity": "warning", "code": "IMPL_NO_LINKS", "message": "Implementation has no outgoing links" },
+        { "rule_id": "field_present", "selector": { "kinds": ["impl"] },
+          "params": { "field": "status" }, "code": "IMPL_STATUS" }
+      ]
+    }"#;
+
+    #[test]
+    fn issue_becomes_result_with_location() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let graph = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(POLICY).unwrap();
+        let reports = core_needle::policy::evaluate_rules(&graph, &policy).unwrap();
+
+        let log = SarifLog::new(
+            &graph,
+            &policy.rules,
+            &policy.defaults,
+            &reports,
+            Some("docs"),
+        );
+        let json = serde_json::to_value(&log).unwrap();
+        let run = &json["runs"][0];
+
+        assert_eq!(json["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "IMPL_NO_LINKS");
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["shortDescription"]["text"],
+            "Implementation has no outgoing links"
+        );
+
+        let result = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["ruleId"] == "IMPL_NO_LINKS")
+            .expect("policy result");
+        assert_eq!(result["level"], "warning");
+        let physical = &result["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "docs/adas/impl.rst");
+        assert_eq!(physical["region"]["startLine"], 7);
+
+        // Rule without a severity falls back to the policy defaults
+        let rules = &run["tool"]["driver"]["rules"];
+        assert_eq!(rules[1]["id"], "IMPL_STATUS");
+        assert_eq!(rules[1]["defaultConfiguration"]["level"], "note");
+        let result = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["ruleId"] == "IMPL_STATUS")
+            .expect("policy result");
+        assert_eq!(result["level"], "note");
+    }
+
+    #[test]
+    fn reports_of_rules_missing_from_the_policy_are_described() {
+        let ds = populate_from_str(NEEDS).unwrap();
+        let graph = Graph::new(ds.access_current_version().unwrap()).unwrap();
+        let policy = load_policy_from_str(POLICY).unwrap();
+        let reports = core_needle::policy::evaluate_rules(&graph, &policy).unwrap();
+
+        let log = SarifLog::new(&graph, &[], &policy.defaults, &reports, None);
+        let json = serde_json::to_value(&log).unwrap();
+        let run = &json["runs"][0];
+
+        let ids: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|r| r["id"].as_str())
+            .collect();
+        assert!(ids.contains(&"IMPL_NO_LINKS"));
+        assert!(ids.contains(&"IMPL_STATUS"));
+    }
+}